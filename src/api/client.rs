@@ -1,58 +1,319 @@
 //! Docker Registry API client
 
-use crate::auth::get_auth_header;
-use crate::models::{AuthConfig, CatalogResponse, Manifest, TagsResponse, BlobInfo};
-use super::ApiError;
+use crate::auth::{
+    cache_token, get_auth_header, get_cached_token, parse_www_authenticate, remember_token_realm,
+    token_cache_key, AuthChallenge, TokenRealm, TokenResponse,
+};
+use crate::models::{ApiRequestInfo, AuthConfig, CatalogResponse, DeleteSupport, Descriptor, ImageConfig, Manifest, ManifestInfo, RateLimitQuota, RegistryConfig, TagInfo, TagsResponse, BlobInfo, TlsOptions, DEFAULT_MAX_RETRIES};
+use crate::utils::{sha256_digest, verify_digest};
+use super::{
+    fail_trace, finish_trace, jittered_backoff, parse_retry_after, rate_limit_observed, rate_limit_quota,
+    record_rate_limit, request_trace, wait_for_retry, ApiError, MAX_RETRY_AFTER,
+};
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, RETRY_AFTER, WWW_AUTHENTICATE};
+
+/// Number of tags resolved concurrently when enriching tag rows
+pub const TAG_INFO_CONCURRENCY: usize = 4;
+
+/// Manifest media types accepted from the registry, including multi-arch lists and indexes
+pub const MANIFEST_ACCEPT: &str = "application/vnd.docker.distribution.manifest.v2+json, \
+application/vnd.docker.distribution.manifest.list.v2+json, \
+application/vnd.oci.image.manifest.v1+json, \
+application/vnd.oci.image.index.v1+json, \
+application/vnd.docker.distribution.manifest.v1+json";
+
+/// OAuth2 client ID sent when exchanging an identity token
+pub const TOKEN_CLIENT_ID: &str = "docker-registry-manager";
+
+/// Well-formed digest that no manifest has, used to probe delete support
+const PROBE_DIGEST: &str = "sha256:0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Registry response read in full, with the trace recorded for it
+struct RegistryResponse {
+    status: u16,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    trace: ApiRequestInfo,
+}
+
+impl RegistryResponse {
+    /// Header value as text
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+    
+    /// Parse the body as JSON
+    fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, ApiError> {
+        serde_json::from_slice(&self.body)
+            .map_err(|e| ApiError::ParseError(e.to_string()).with_trace(self.trace.clone()))
+    }
+}
 
 /// Docker Registry API client
 pub struct RegistryClient {
     base_url: String,
     auth: AuthConfig,
     client: reqwest::Client,
+    max_retries: u32,
 }
 
 impl RegistryClient {
+    /// Create a client for a saved registry, using its retry setting
+    pub fn for_registry(registry: &RegistryConfig) -> Result<Self, ApiError> {
+        Ok(Self::new(registry.url.clone(), registry.auth.clone(), registry.tls.clone())?
+            .with_max_retries(registry.max_retries))
+    }
+    
     /// Create a new registry client
-    pub fn new(base_url: String, auth: AuthConfig) -> Result<Self, ApiError> {
-        let client = reqwest::Client::builder()
-            .build()
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
-        
+    pub fn new(base_url: String, auth: AuthConfig, tls: TlsOptions) -> Result<Self, ApiError> {
         // Normalize URL - remove trailing slash
         let base_url = base_url.trim_end_matches('/').to_string();
         
+        if base_url.starts_with("http://") && !tls.allow_http {
+            return Err(ApiError::InvalidUrl(format!(
+                "Plain HTTP is not allowed for {}. Enable \"Allow plain HTTP\" in the registry settings.",
+                base_url
+            )));
+        }
+        
+        let mut builder = with_tls_options(reqwest::Client::builder(), &tls)?;
+        
+        // Client certificates are presented during the TLS handshake, not via a header
+        if let AuthConfig::TlsCert { cert_path, key_path } = &auth {
+            builder = with_client_identity(builder, cert_path, key_path)?;
+        }
+        
+        let client = builder
+            .build()
+            .map_err(|e| build_error(&auth, &e))?;
+        
         Ok(Self {
             base_url,
             auth,
             client,
+            max_retries: DEFAULT_MAX_RETRIES,
         })
     }
     
+    /// Retry throttled or failed GET/HEAD requests up to `max_retries` times
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+    
+    /// Requests allowed for one operation, the first one included
+    pub fn max_attempts(&self) -> u32 {
+        self.max_retries + 1
+    }
+    
     /// Build a request with authentication
     fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
         let url = format!("{}{}", self.base_url, path);
-        let mut req = self.client.request(method, &url);
+        let mut req = self.client.request(method.clone(), &url);
         
-        if let Some(auth_header) = get_auth_header(&self.auth) {
-            req = req.header("Authorization", auth_header);
+        // Prefer a cached bearer token for this scope over the static credentials
+        let cached = self.uses_token_flow()
+            .then(|| request_scope(&method, path))
+            .flatten()
+            .and_then(|scope| get_cached_token(&self.token_key(&scope)));
+        
+        if let Some(token) = cached {
+            req = req.header(AUTHORIZATION, format!("Bearer {}", token));
+        } else if let Some(auth_header) = get_auth_header(&self.auth) {
+            req = req.header(AUTHORIZATION, auth_header);
         }
         
         req
     }
     
+    /// Send a request, retrying idempotent ones that were throttled or failed transiently
+    ///
+    /// GET and HEAD requests are retried up to `max_retries` times on 429, 502-504 and
+    /// network errors, waiting for `Retry-After` when given and for a jittered exponential
+    /// backoff otherwise. A `Retry-After` beyond `MAX_RETRY_AFTER` is left to the caller.
+    async fn send(&self, builder: reqwest::RequestBuilder) -> Result<RegistryResponse, ApiError> {
+        let request = builder
+            .build()
+            .map_err(|e| ApiError::InvalidUrl(e.to_string()))?;
+        
+        let idempotent = request.method() == reqwest::Method::GET || request.method() == reqwest::Method::HEAD;
+        if !idempotent || self.max_retries == 0 {
+            return self.send_once(request).await;
+        }
+        
+        let mut attempt = 0;
+        loop {
+            let Some(retry) = request.try_clone() else {
+                return self.send_once(request).await;
+            };
+            attempt += 1;
+            
+            let result = self.send_once(retry).await;
+            let (status, delay) = match &result {
+                Ok(response) if is_retryable_status(response.status) => (
+                    Some(response.status),
+                    retry_after(response).unwrap_or_else(|| jittered_backoff(attempt)),
+                ),
+                Err(e) if matches!(e.kind(), ApiError::NetworkError(_)) => (None, jittered_backoff(attempt)),
+                _ => return result,
+            };
+            
+            if attempt > self.max_retries || delay > MAX_RETRY_AFTER {
+                return result;
+            }
+            wait_for_retry(&self.base_url, status, attempt, self.max_retries, delay).await;
+        }
+    }
+    
+    /// Send a request once, performing the token handshake on a Bearer challenge
+    ///
+    /// A 401 carrying `WWW-Authenticate: Bearer realm=...` makes the client fetch a token
+    /// from the realm, cache it per scope and replay the original request once.
+    async fn send_once(&self, request: reqwest::Request) -> Result<RegistryResponse, ApiError> {
+        let replay = request.try_clone();
+        
+        let response = self.execute(request, false).await?;
+        
+        if response.status != 401 || !self.uses_token_flow() {
+            return Ok(response);
+        }
+        
+        let challenge = response
+            .header(WWW_AUTHENTICATE.as_str())
+            .and_then(parse_www_authenticate)
+            .filter(|c| c.is_bearer() && c.realm().is_some());
+        
+        let (Some(challenge), Some(mut replay)) = (challenge, replay) else {
+            return Ok(response);
+        };
+        
+        let token = self.fetch_token(&challenge).await?;
+        let value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        replay.headers_mut().insert(AUTHORIZATION, value);
+        
+        self.execute(replay, false).await
+    }
+    
+    /// Execute a request, read the whole response and record its trace
+    ///
+    /// `secret_body` keeps the response body out of the trace (token responses).
+    async fn execute(&self, request: reqwest::Request, secret_body: bool) -> Result<RegistryResponse, ApiError> {
+        let mut trace = request_trace(&request);
+        
+        let response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                fail_trace(&mut trace, &e.to_string());
+                return Err(ApiError::NetworkError(e.to_string()).with_trace(trace));
+            }
+        };
+        
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = match response.bytes().await {
+            Ok(body) => body.to_vec(),
+            Err(e) => {
+                fail_trace(&mut trace, &e.to_string());
+                return Err(ApiError::NetworkError(e.to_string()).with_trace(trace));
+            }
+        };
+        
+        let traced_body = if secret_body && status == 200 { None } else { Some(body.as_slice()) };
+        finish_trace(&mut trace, status, &headers, traced_body);
+        
+        Ok(RegistryResponse { status, headers, body, trace })
+    }
+    
+    /// Fetch a bearer token from the challenge realm and cache it
+    async fn fetch_token(&self, challenge: &AuthChallenge) -> Result<String, ApiError> {
+        let realm = challenge.realm().unwrap_or_default();
+        let scope = challenge.scope().unwrap_or_default();
+        remember_token_realm(&self.base_url, TokenRealm {
+            realm: realm.to_string(),
+            service: challenge.service().map(str::to_string),
+        });
+        
+        let mut query = Vec::new();
+        if let Some(service) = challenge.service() {
+            query.push(("service", service));
+        }
+        // Scopes may be space separated; each one is sent as its own parameter
+        for s in scope.split_whitespace() {
+            query.push(("scope", s));
+        }
+        
+        let req = match &self.auth {
+            // Identity tokens are refresh tokens for the OAuth2 token endpoint
+            AuthConfig::IdentityToken { token, .. } => {
+                let scopes = scope.split_whitespace().collect::<Vec<_>>().join(" ");
+                let mut form = vec![
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", token.as_str()),
+                    ("client_id", TOKEN_CLIENT_ID),
+                    ("scope", scopes.as_str()),
+                ];
+                if let Some(service) = challenge.service() {
+                    form.push(("service", service));
+                }
+                self.client.post(realm).form(&form)
+            }
+            AuthConfig::BasicAuth { username, password, .. } => {
+                self.client.get(realm).query(&query).basic_auth(username, Some(password))
+            }
+            _ => self.client.get(realm).query(&query),
+        };
+        
+        let request = req
+            .build()
+            .map_err(|e| ApiError::InvalidUrl(e.to_string()))?;
+        let response = self.execute(request, true).await?;
+        
+        if response.status != 200 {
+            return Err(error_from_response(&response, format!("Failed to get token from {}", realm)));
+        }
+        
+        let token_response: TokenResponse = response.json()?;
+        
+        let token = token_response
+            .bearer_token()
+            .ok_or_else(|| ApiError::ParseError("Token response contains no token".to_string()))?
+            .to_string();
+        
+        cache_token(self.token_key(scope), token.clone(), token_response.expires_in);
+        Ok(token)
+    }
+    
+    /// Whether this client may exchange its credentials for bearer tokens
+    fn uses_token_flow(&self) -> bool {
+        matches!(
+            self.auth,
+            AuthConfig::Anonymous | AuthConfig::BasicAuth { .. } | AuthConfig::IdentityToken { .. }
+        )
+    }
+    
+    /// Token cache key for a scope, bound to this registry and identity
+    fn token_key(&self, scope: &str) -> String {
+        let identity = match &self.auth {
+            AuthConfig::BasicAuth { username, .. } => username.as_str(),
+            // Cannot clash with a username, which may not contain ':'
+            AuthConfig::IdentityToken { .. } => "identity:token",
+            _ => "",
+        };
+        token_cache_key(&self.base_url, identity, scope)
+    }
+    
     /// Check registry availability (ping)
     pub async fn ping(&self) -> Result<(), ApiError> {
-        let response = self.request(reqwest::Method::GET, "/v2/")
-            .send()
-            .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let response = self.send(self.request(reqwest::Method::GET, "/v2/")).await?;
         
-        let status = response.status().as_u16();
+        let status = response.status;
         if status == 200 || status == 401 {
             // 401 means registry is available but needs auth
             Ok(())
         } else {
-            Err(ApiError::from_status(status, "Registry not available".to_string()))
+            Err(error_from_response(&response, "Registry not available".to_string()))
         }
     }
     
@@ -63,126 +324,341 @@ impl RegistryClient {
             None => "/v2/_catalog".to_string(),
         };
         
-        let response = self.request(reqwest::Method::GET, &path)
-            .send()
-            .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let response = self.send(self.request(reqwest::Method::GET, &path)).await?;
         
-        let status = response.status().as_u16();
+        let status = response.status;
         if status != 200 {
-            return Err(ApiError::from_status(status, "Failed to get catalog".to_string()));
+            return Err(error_from_response(&response, "Failed to get catalog".to_string()));
         }
         
         // Check for pagination Link header
-        let next_page = response
-            .headers()
-            .get("Link")
-            .and_then(|v| v.to_str().ok())
-            .and_then(parse_link_header);
-        
-        let mut catalog: CatalogResponse = response
-            .json()
-            .await
-            .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        let next_page = response.header("Link").and_then(parse_link_header);
+        
+        let mut catalog: CatalogResponse = response.json()?;
         
         catalog.next_page = next_page;
         Ok(catalog)
     }
     
+    /// List every repository by following catalog pagination to completion
+    pub async fn list_all_repositories(&self, page_size: usize) -> Result<Vec<String>, ApiError> {
+        let mut repositories = Vec::new();
+        self.list_all_repositories_with(page_size, |page| repositories.extend_from_slice(page))
+            .await?;
+        Ok(repositories)
+    }
+    
+    /// List every repository, calling `on_page` as each catalog page arrives
+    pub async fn list_all_repositories_with<F>(&self, page_size: usize, mut on_page: F) -> Result<(), ApiError>
+    where
+        F: FnMut(&[String]),
+    {
+        let mut query = Some(format!("n={}", page_size.max(1)));
+        
+        while let Some(current) = query.take() {
+            let catalog = self.get_catalog(Some(&current)).await?;
+            on_page(&catalog.repositories);
+            
+            // Stop on an empty page or a server that keeps returning the same link
+            if catalog.repositories.is_empty() {
+                break;
+            }
+            query = catalog.next_page.filter(|next| *next != current);
+        }
+        
+        Ok(())
+    }
+    
     /// Get tags for a repository
-    pub async fn get_tags(&self, repo: &str) -> Result<TagsResponse, ApiError> {
-        let path = format!("/v2/{}/tags/list", repo);
+    pub async fn get_tags(&self, repo: &str, page: Option<&str>) -> Result<TagsResponse, ApiError> {
+        let path = match page {
+            Some(p) => format!("/v2/{}/tags/list?{}", repo, p),
+            None => format!("/v2/{}/tags/list", repo),
+        };
         
-        let response = self.request(reqwest::Method::GET, &path)
-            .send()
-            .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let response = self.send(self.request(reqwest::Method::GET, &path)).await?;
         
-        let status = response.status().as_u16();
+        let status = response.status;
         if status != 200 {
-            return Err(ApiError::from_status(status, format!("Failed to get tags for {}", repo)));
+            return Err(error_from_response(&response, format!("Failed to get tags for {}", repo)));
         }
         
-        response
-            .json()
-            .await
-            .map_err(|e| ApiError::ParseError(e.to_string()))
+        // Check for pagination Link header
+        let next_page = response.header("Link").and_then(parse_link_header);
+        
+        let mut tags: TagsResponse = response.json()?;
+        
+        tags.next_page = next_page;
+        Ok(tags)
+    }
+    
+    /// List every tag of a repository by following tags/list pagination to completion
+    pub async fn list_all_tags(&self, repo: &str, page_size: usize) -> Result<Vec<String>, ApiError> {
+        let mut tags = Vec::new();
+        self.list_all_tags_with(repo, page_size, |page| tags.extend_from_slice(page))
+            .await?;
+        Ok(tags)
+    }
+    
+    /// List every tag of a repository, calling `on_page` as each page arrives
+    pub async fn list_all_tags_with<F>(&self, repo: &str, page_size: usize, mut on_page: F) -> Result<(), ApiError>
+    where
+        F: FnMut(&[String]),
+    {
+        let mut query = Some(format!("n={}", page_size.max(1)));
+        
+        while let Some(current) = query.take() {
+            let response = self.get_tags(repo, Some(&current)).await?;
+            let tags = response.tags.unwrap_or_default();
+            on_page(&tags);
+            
+            // Stop on an empty page or a server that keeps returning the same link
+            if tags.is_empty() {
+                break;
+            }
+            query = response.next_page.filter(|next| *next != current);
+        }
+        
+        Ok(())
     }
     
     /// Get manifest by reference (tag or digest)
     pub async fn get_manifest(&self, repo: &str, reference: &str) -> Result<(Manifest, String), ApiError> {
         let path = format!("/v2/{}/manifests/{}", repo, reference);
         
-        let response = self.request(reqwest::Method::GET, &path)
-            .header("Accept", "application/vnd.docker.distribution.manifest.v2+json, application/vnd.oci.image.manifest.v1+json, application/vnd.docker.distribution.manifest.v1+json")
-            .send()
-            .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let request = self.request(reqwest::Method::GET, &path)
+            .header("Accept", MANIFEST_ACCEPT);
+        let response = self.send(request).await?;
         
-        let status = response.status().as_u16();
+        record_rate_limit(&self.base_url, &response.headers);
+        let status = response.status;
         if status != 200 {
-            return Err(ApiError::from_status(status, format!("Failed to get manifest for {}:{}", repo, reference)));
+            return Err(error_from_response(&response, format!("Failed to get manifest for {}:{}", repo, reference)));
         }
         
-        // Get digest from header
-        let digest = response
-            .headers()
-            .get("Docker-Content-Digest")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string())
-            .unwrap_or_default();
+        let manifest: Manifest = response.json()?;
         
-        let manifest: Manifest = response
-            .json()
-            .await
-            .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        // Some registries omit the header; the digest is the sha256 of the exact bytes served
+        let digest = content_digest(&response).unwrap_or_else(|| sha256_digest(&response.body));
         
         Ok((manifest, digest))
     }
     
+    /// Get manifest digest, media type and size without downloading it (HEAD request)
+    ///
+    /// Falls back to a GET and hashes the body when `Docker-Content-Digest` is missing.
+    pub async fn head_manifest(&self, repo: &str, reference: &str) -> Result<ManifestInfo, ApiError> {
+        let path = format!("/v2/{}/manifests/{}", repo, reference);
+        
+        let request = self.request(reqwest::Method::HEAD, &path)
+            .header("Accept", MANIFEST_ACCEPT);
+        let response = self.send(request).await?;
+        
+        record_rate_limit(&self.base_url, &response.headers);
+        let status = response.status;
+        if status != 200 {
+            return Err(error_from_response(&response, format!("Failed to get manifest for {}:{}", repo, reference)));
+        }
+        
+        let media_type = content_type(&response);
+        let size = response
+            .header("Content-Length")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        
+        if let Some(digest) = content_digest(&response) {
+            return Ok(ManifestInfo { digest, media_type, size });
+        }
+        
+        let request = self.request(reqwest::Method::GET, &path)
+            .header("Accept", MANIFEST_ACCEPT);
+        let response = self.send(request).await?;
+        
+        record_rate_limit(&self.base_url, &response.headers);
+        let status = response.status;
+        if status != 200 {
+            return Err(error_from_response(&response, format!("Failed to get manifest for {}:{}", repo, reference)));
+        }
+        
+        Ok(ManifestInfo {
+            digest: sha256_digest(&response.body),
+            media_type: content_type(&response),
+            size: response.body.len() as u64,
+        })
+    }
+    
+    /// Resolve digest, compressed size and creation time of a tag
+    ///
+    /// Multi-arch tags are described by their default platform (see
+    /// `Manifest::default_platform_manifest`), while the digest stays the index digest.
+    pub async fn get_tag_info(&self, repo: &str, tag: &str) -> Result<TagInfo, ApiError> {
+        let (manifest, digest) = self.get_manifest(repo, tag).await?;
+        
+        let image = match manifest.default_platform_manifest() {
+            Some(child) => self.get_manifest(repo, &child.digest).await?.0,
+            None => manifest,
+        };
+        
+        // Creation time is best effort; a missing config blob should not hide the size
+        let created = match image.config() {
+            Some(config) => self.get_config(repo, config).await.ok().and_then(|c| c.created),
+            None => None,
+        };
+        
+        Ok(TagInfo {
+            name: tag.to_string(),
+            digest,
+            size: image.total_size(),
+            created,
+        })
+    }
+    
+    /// Last pull quota reported by this registry, if it reports one
+    pub fn rate_limit(&self) -> Option<RateLimitQuota> {
+        rate_limit_quota(&self.base_url)
+    }
+    
+    /// Check whether resolving `tags` tags with `get_tag_info` would use up the
+    /// registry's pull quota
+    ///
+    /// `repo:reference` is probed with a HEAD request, which Docker Hub does not count
+    /// as a pull. It learns the quota and whether tags are multi-arch indexes, which
+    /// cost two manifest GETs each instead of one. Returns the quota when it would be
+    /// exhausted.
+    pub async fn quota_shortfall(&self, repo: &str, reference: &str, tags: usize) -> Option<RateLimitQuota> {
+        // Registries without a quota are known after their first manifest response
+        if rate_limit_observed(&self.base_url) && self.rate_limit().is_none() {
+            return None;
+        }
+        
+        // Assume indexes when the probe fails
+        let per_tag = match self.head_manifest(repo, reference).await {
+            Ok(info) if !info.is_index() => 1,
+            _ => 2,
+        };
+        self.rate_limit().filter(|quota| quota.would_exhaust(tags * per_tag))
+    }
+    
+    /// Resolve tag info for many tags with bounded concurrency
+    ///
+    /// `on_result` is called with the tag name and its result as each lookup completes.
+    pub async fn get_tag_infos_with<F>(&self, repo: &str, tags: Vec<String>, concurrency: usize, mut on_result: F)
+    where
+        F: FnMut(String, Result<TagInfo, ApiError>),
+    {
+        let mut results = stream::iter(tags)
+            .map(|name| async move {
+                let result = self.get_tag_info(repo, &name).await;
+                (name, result)
+            })
+            .buffer_unordered(concurrency.max(1));
+        
+        while let Some((name, result)) = results.next().await {
+            on_result(name, result);
+        }
+    }
+    
+    /// Resolve manifest digests for many tags with bounded concurrency (HEAD requests)
+    ///
+    /// `on_result` is called with the tag name and its result as each lookup completes.
+    pub async fn head_manifests_with<F>(&self, repo: &str, tags: Vec<String>, concurrency: usize, mut on_result: F)
+    where
+        F: FnMut(String, Result<ManifestInfo, ApiError>),
+    {
+        let mut results = stream::iter(tags)
+            .map(|name| async move {
+                let result = self.head_manifest(repo, &name).await;
+                (name, result)
+            })
+            .buffer_unordered(concurrency.max(1));
+        
+        while let Some((name, result)) = results.next().await {
+            on_result(name, result);
+        }
+    }
+    
     /// Delete manifest by digest
     pub async fn delete_manifest(&self, repo: &str, digest: &str) -> Result<(), ApiError> {
         let path = format!("/v2/{}/manifests/{}", repo, digest);
         
-        let response = self.request(reqwest::Method::DELETE, &path)
-            .send()
-            .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let response = self.send(self.request(reqwest::Method::DELETE, &path)).await?;
         
-        let status = response.status().as_u16();
+        let status = response.status;
+        if status == 405 {
+            return Err(ApiError::MethodNotAllowed(
+                "Deleting manifests is disabled on this registry (REGISTRY_STORAGE_DELETE_ENABLED)".to_string(),
+            )
+            .with_trace(response.trace));
+        }
         if status != 202 && status != 200 {
-            return Err(ApiError::from_status(status, format!("Failed to delete manifest {}", digest)));
+            return Err(error_from_response(&response, format!("Failed to delete manifest {}", digest)));
         }
         
         Ok(())
     }
     
+    /// Find out whether the registry accepts deletes without deleting anything
+    ///
+    /// Sends a DELETE for a digest that cannot exist in `repo`: a registry with
+    /// deletion disabled answers 405, one with deletion enabled answers 404.
+    pub async fn probe_delete_support(&self, repo: &str) -> Result<DeleteSupport, ApiError> {
+        let path = format!("/v2/{}/manifests/{}", repo, PROBE_DIGEST);
+        
+        let response = self.send(self.request(reqwest::Method::DELETE, &path)).await?;
+        
+        match response.status {
+            405 => Ok(DeleteSupport::Disabled),
+            401 | 403 => Ok(DeleteSupport::Denied),
+            200 | 202 | 404 => Ok(DeleteSupport::Enabled),
+            _ => Err(error_from_response(&response, "Failed to probe delete support".to_string())),
+        }
+    }
+    
+    /// Download a blob and verify its content against the digest
+    pub async fn get_blob(&self, repo: &str, digest: &str) -> Result<Vec<u8>, ApiError> {
+        let path = format!("/v2/{}/blobs/{}", repo, digest);
+        
+        let response = self.send(self.request(reqwest::Method::GET, &path)).await?;
+        
+        let status = response.status;
+        if status != 200 {
+            return Err(error_from_response(&response, format!("Failed to get blob {}", digest)));
+        }
+        
+        verify_digest(&response.body, digest).map_err(|actual| {
+            ApiError::DigestMismatch {
+                expected: digest.to_string(),
+                actual,
+            }
+            .with_trace(response.trace.clone())
+        })?;
+        
+        Ok(response.body)
+    }
+    
+    /// Download and parse the image config blob referenced by a manifest
+    pub async fn get_config(&self, repo: &str, config: &Descriptor) -> Result<ImageConfig, ApiError> {
+        let data = self.get_blob(repo, &config.digest).await?;
+        serde_json::from_slice(&data).map_err(|e| ApiError::ParseError(e.to_string()))
+    }
+    
     /// Get blob info (HEAD request)
     pub async fn head_blob(&self, repo: &str, digest: &str) -> Result<BlobInfo, ApiError> {
         let path = format!("/v2/{}/blobs/{}", repo, digest);
         
-        let response = self.request(reqwest::Method::HEAD, &path)
-            .send()
-            .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let response = self.send(self.request(reqwest::Method::HEAD, &path)).await?;
         
-        let status = response.status().as_u16();
+        let status = response.status;
         if status != 200 {
-            return Err(ApiError::from_status(status, format!("Failed to get blob {}", digest)));
+            return Err(error_from_response(&response, format!("Failed to get blob {}", digest)));
         }
         
         let size = response
-            .headers()
-            .get("Content-Length")
-            .and_then(|v| v.to_str().ok())
+            .header("Content-Length")
             .and_then(|s| s.parse().ok())
             .unwrap_or(0);
         
-        let media_type = response
-            .headers()
-            .get("Content-Type")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
+        let media_type = response.header("Content-Type").map(|s| s.to_string());
         
         Ok(BlobInfo {
             digest: digest.to_string(),
@@ -192,9 +668,178 @@ impl RegistryClient {
     }
 }
 
+/// Read the `Docker-Content-Digest` header
+fn content_digest(response: &RegistryResponse) -> Option<String> {
+    response
+        .header("Docker-Content-Digest")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Build an error from a failed response, keeping the registry's error body and the trace
+fn error_from_response(response: &RegistryResponse, message: String) -> ApiError {
+    let error = match ApiError::from_response(response.status, message, &response.body) {
+        ApiError::RateLimited(default) => ApiError::RateLimited(
+            retry_after(response).map(|d| d.as_secs_f64().ceil() as u64).unwrap_or(default),
+        ),
+        error => error,
+    };
+    error.with_trace(response.trace.clone())
+}
+
+/// Read the `Retry-After` header (delay seconds or HTTP-date)
+fn retry_after(response: &RegistryResponse) -> Option<std::time::Duration> {
+    response.header(RETRY_AFTER.as_str()).and_then(parse_retry_after)
+}
+
+/// Statuses worth retrying an idempotent request for
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 502 | 503 | 504)
+}
+
+/// Read the media type from `Content-Type`, dropping any parameters
+fn content_type(response: &RegistryResponse) -> String {
+    response
+        .header("Content-Type")
+        .and_then(|s| s.split(';').next())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Apply per-registry CA bundle, verification and plain-HTTP settings (Desktop)
+#[cfg(not(target_arch = "wasm32"))]
+fn with_tls_options(
+    mut builder: reqwest::ClientBuilder,
+    tls: &TlsOptions,
+) -> Result<reqwest::ClientBuilder, ApiError> {
+    if !tls.ca_bundle_path.trim().is_empty() {
+        let pem = std::fs::read(&tls.ca_bundle_path).map_err(|e| {
+            ApiError::TlsError(format!("Cannot read CA bundle '{}': {}", tls.ca_bundle_path, e))
+        })?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| ApiError::TlsError(format!("Invalid CA bundle: {}", error_chain(&e))))?;
+        if certs.is_empty() {
+            return Err(ApiError::TlsError(format!(
+                "CA bundle '{}' contains no certificates",
+                tls.ca_bundle_path
+            )));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    
+    if tls.insecure_skip_verify {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    
+    // Also refuses redirects from HTTPS to plain HTTP
+    Ok(builder.https_only(!tls.allow_http))
+}
+
+/// The browser owns certificate validation, so only plain-HTTP settings apply on web
+#[cfg(target_arch = "wasm32")]
+fn with_tls_options(
+    builder: reqwest::ClientBuilder,
+    tls: &TlsOptions,
+) -> Result<reqwest::ClientBuilder, ApiError> {
+    if !tls.ca_bundle_path.trim().is_empty() || tls.insecure_skip_verify {
+        return Err(ApiError::Unsupported(
+            "Custom CA bundles and skipping certificate verification are only available in the desktop app".to_string(),
+        ));
+    }
+    Ok(builder)
+}
+
+/// Load a PEM client certificate and key into the client builder (Desktop only)
+#[cfg(not(target_arch = "wasm32"))]
+fn with_client_identity(
+    builder: reqwest::ClientBuilder,
+    cert_path: &str,
+    key_path: &str,
+) -> Result<reqwest::ClientBuilder, ApiError> {
+    let read_pem = |path: &str, what: &str| {
+        if path.trim().is_empty() {
+            return Err(ApiError::TlsError(format!("Client {} path is not set", what)));
+        }
+        std::fs::read(path)
+            .map_err(|e| ApiError::TlsError(format!("Cannot read client {} '{}': {}", what, path, e)))
+    };
+    
+    // reqwest expects the certificate chain and private key in a single PEM buffer
+    let mut pem = read_pem(cert_path, "certificate")?;
+    pem.push(b'\n');
+    pem.extend(read_pem(key_path, "key")?);
+    
+    let identity = reqwest::Identity::from_pem(&pem)
+        .map_err(|e| ApiError::TlsError(format!("Invalid client certificate or key: {}", error_chain(&e))))?;
+    
+    Ok(builder.identity(identity))
+}
+
+/// Client certificates cannot be configured from the browser
+#[cfg(target_arch = "wasm32")]
+fn with_client_identity(
+    _builder: reqwest::ClientBuilder,
+    _cert_path: &str,
+    _key_path: &str,
+) -> Result<reqwest::ClientBuilder, ApiError> {
+    Err(ApiError::Unsupported(
+        "TLS client certificates are only available in the desktop app".to_string(),
+    ))
+}
+
+/// Explain a client build failure
+///
+/// Only blames the client certificate when a client with nothing but the identity
+/// fails to build as well; other failures, e.g. from the CA bundle, pass through.
+fn build_error(auth: &AuthConfig, error: &reqwest::Error) -> ApiError {
+    if let AuthConfig::TlsCert { cert_path, key_path } = auth {
+        let identity_only = with_client_identity(reqwest::Client::builder(), cert_path, key_path)
+            .and_then(|builder| builder.build().map_err(|e| ApiError::TlsError(error_chain(&e))));
+        if let Err(ApiError::TlsError(cause)) = identity_only {
+            return ApiError::TlsError(format!("Client certificate and key do not match or are invalid: {}", cause));
+        }
+    }
+    ApiError::NetworkError(error.to_string())
+}
+
+/// Format an error together with its sources (reqwest hides the TLS cause)
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Derive the token scope a request will be challenged for
+///
+/// Used to look up cached tokens before sending; the challenge returned by the
+/// registry remains authoritative when no cached token matches.
+pub fn request_scope(method: &reqwest::Method, path: &str) -> Option<String> {
+    let path = path.split('?').next()?;
+    if path == "/v2/_catalog" {
+        return Some("registry:catalog:*".to_string());
+    }
+    
+    let rest = path.strip_prefix("/v2/")?;
+    let repo = ["/manifests/", "/blobs/", "/tags/list"]
+        .iter()
+        .filter_map(|marker| rest.find(marker))
+        .min()
+        .map(|end| &rest[..end])?;
+    
+    let action = if *method == reqwest::Method::DELETE { "delete" } else { "pull" };
+    Some(format!("repository:{}:{}", repo, action))
+}
+
 /// Parse Link header for pagination
 fn parse_link_header(header: &str) -> Option<String> {
-    // Format: </v2/_catalog?n=100&last=repo>; rel="next"
+    // Format: </v2/_catalog?n=100&last=repo>; rel="next" (tags/list uses the same form)
     for part in header.split(',') {
         if part.contains("rel=\"next\"") || part.contains("rel=next") {
             let url_part = part.split(';').next()?;
@@ -1,6 +1,7 @@
 //! API error types
 
 use thiserror::Error;
+use crate::models::{ApiRequestInfo, AppError, ErrorCode, ErrorDetails, RegistryError, RegistryErrorCode, RegistryErrorEnvelope};
 
 /// API error types
 #[derive(Error, Debug, Clone)]
@@ -28,6 +29,37 @@ pub enum ApiError {
     
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+    
+    #[error("Digest mismatch: expected {expected}, got {actual}")]
+    DigestMismatch { expected: String, actual: String },
+    
+    #[error("TLS configuration error: {0}")]
+    TlsError(String),
+    
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+    
+    #[error("Operation not allowed by the registry: {0}")]
+    MethodNotAllowed(String),
+    
+    #[error("{message}: {}", describe_errors(.errors))]
+    Registry {
+        status: u16,
+        message: String,
+        errors: Vec<RegistryError>,
+    },
+    
+    /// Error with the trace of the request that caused it
+    #[error("{error}")]
+    Traced {
+        error: Box<ApiError>,
+        trace: Box<ApiRequestInfo>,
+    },
+}
+
+/// Join registry errors into a single line
+fn describe_errors(errors: &[RegistryError]) -> String {
+    errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
 }
 
 impl ApiError {
@@ -37,9 +69,162 @@ impl ApiError {
             401 => ApiError::Unauthorized,
             403 => ApiError::Forbidden,
             404 => ApiError::NotFound(message),
+            405 => ApiError::MethodNotAllowed(message),
             429 => ApiError::RateLimited(60), // Default retry after
             500..=599 => ApiError::ServerError(message),
             _ => ApiError::NetworkError(message),
         }
     }
+    
+    /// Create an error from HTTP status code and response body
+    ///
+    /// Uses the registry's error envelope when the body has one, so the
+    /// reported reason (DENIED, NAME_UNKNOWN, ...) is not lost.
+    pub fn from_response(status: u16, message: String, body: &[u8]) -> Self {
+        let errors = serde_json::from_slice::<RegistryErrorEnvelope>(body)
+            .map(|envelope| envelope.errors)
+            .unwrap_or_default();
+        
+        // Rate limiting keeps its own variant so retry handling still applies
+        if errors.is_empty() || status == 429 {
+            return Self::from_status(status, message);
+        }
+        
+        ApiError::Registry { status, message, errors }
+    }
+    
+    /// Attach the trace of the request that caused the error
+    pub fn with_trace(self, trace: ApiRequestInfo) -> Self {
+        ApiError::Traced {
+            error: Box::new(self.kind().clone()),
+            trace: Box::new(trace),
+        }
+    }
+    
+    /// The error itself, without an attached trace
+    pub fn kind(&self) -> &ApiError {
+        match self {
+            ApiError::Traced { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+    
+    /// Trace of the request that caused the error, if one was recorded
+    pub fn trace(&self) -> Option<&ApiRequestInfo> {
+        match self {
+            ApiError::Traced { trace, .. } => Some(trace),
+            _ => None,
+        }
+    }
+    
+    /// HTTP status associated with the error, if any
+    pub fn status_code(&self) -> Option<u16> {
+        match self.kind() {
+            ApiError::Unauthorized => Some(401),
+            ApiError::Forbidden => Some(403),
+            ApiError::NotFound(_) => Some(404),
+            ApiError::MethodNotAllowed(_) => Some(405),
+            ApiError::RateLimited(_) => Some(429),
+            ApiError::Registry { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+    
+    /// Errors reported by the registry, empty when the body had none
+    pub fn registry_errors(&self) -> &[RegistryError] {
+        match self.kind() {
+            ApiError::Registry { errors, .. } => errors,
+            _ => &[],
+        }
+    }
+    
+    /// Whether the requested resource does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.kind(), ApiError::NotFound(_)) || self.status_code() == Some(404)
+    }
+    
+    /// Whether the request may succeed when retried (5xx and 429)
+    pub fn is_transient(&self) -> bool {
+        match self.kind() {
+            ApiError::ServerError(_) | ApiError::RateLimited(_) => true,
+            ApiError::Registry { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+    
+    /// Convert into an application error for display
+    pub fn to_app_error(&self) -> AppError {
+        let error = self.kind();
+        let code = match error {
+            ApiError::NetworkError(_) | ApiError::InvalidUrl(_) | ApiError::TlsError(_) => ErrorCode::NetworkError,
+            ApiError::Unauthorized => ErrorCode::AuthenticationError,
+            ApiError::Forbidden => ErrorCode::Forbidden,
+            ApiError::NotFound(_) => ErrorCode::NotFound,
+            ApiError::RateLimited(_) => ErrorCode::RateLimited,
+            ApiError::ServerError(_) => ErrorCode::ServerError,
+            ApiError::ParseError(_) | ApiError::DigestMismatch { .. } => ErrorCode::ParseError,
+            ApiError::Unsupported(_) | ApiError::MethodNotAllowed(_) => ErrorCode::Unsupported,
+            ApiError::Registry { status, errors, .. } => registry_error_code(*status, errors),
+            ApiError::Traced { .. } => unreachable!("kind() removes traces"),
+        };
+        
+        // Registry errors are listed separately in the details
+        let message = match error {
+            ApiError::Registry { message, .. } => message.clone(),
+            _ => error.to_string(),
+        };
+        
+        let mut details = ErrorDetails {
+            status_code: self.status_code(),
+            registry_errors: self.registry_errors().to_vec(),
+            ..Default::default()
+        };
+        if let Some(trace) = self.trace() {
+            details.status_code = trace.status_code.or(details.status_code);
+            details.response_headers = trace.response_headers.clone();
+            details.response_body = trace.response_body.clone();
+            details.curl_command = Some(trace.curl_command.clone());
+        }
+        
+        let app_error = AppError::new(code, message);
+        let app_error = if details == ErrorDetails::default() {
+            app_error
+        } else {
+            app_error.with_details(details)
+        };
+        
+        match error {
+            ApiError::MethodNotAllowed(_) | ApiError::Unsupported(_) => app_error.non_recoverable(),
+            _ => app_error,
+        }
+    }
+}
+
+/// Pick an error category from the first registry error, falling back to the status
+fn registry_error_code(status: u16, errors: &[RegistryError]) -> ErrorCode {
+    match errors.first().map(|e| &e.code) {
+        Some(RegistryErrorCode::Unauthorized) => ErrorCode::AuthenticationError,
+        Some(RegistryErrorCode::Denied) => ErrorCode::Forbidden,
+        Some(RegistryErrorCode::BlobUnknown)
+        | Some(RegistryErrorCode::BlobUploadUnknown)
+        | Some(RegistryErrorCode::ManifestUnknown)
+        | Some(RegistryErrorCode::ManifestBlobUnknown)
+        | Some(RegistryErrorCode::NameUnknown) => ErrorCode::NotFound,
+        Some(RegistryErrorCode::NameInvalid)
+        | Some(RegistryErrorCode::TagInvalid)
+        | Some(RegistryErrorCode::DigestInvalid)
+        | Some(RegistryErrorCode::SizeInvalid)
+        | Some(RegistryErrorCode::ManifestInvalid)
+        | Some(RegistryErrorCode::ManifestUnverified)
+        | Some(RegistryErrorCode::BlobUploadInvalid) => ErrorCode::ValidationError,
+        Some(RegistryErrorCode::Unsupported) => ErrorCode::Unsupported,
+        Some(RegistryErrorCode::TooManyRequests) => ErrorCode::RateLimited,
+        _ => match status {
+            401 => ErrorCode::AuthenticationError,
+            403 => ErrorCode::Forbidden,
+            404 => ErrorCode::NotFound,
+            500..=599 => ErrorCode::ServerError,
+            _ => ErrorCode::NetworkError,
+        },
+    }
 }
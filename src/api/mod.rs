@@ -2,6 +2,12 @@
 
 mod client;
 mod error;
+mod retry;
+mod rate_limit;
+mod trace;
 
 pub use client::*;
 pub use error::*;
+pub use retry::*;
+pub use rate_limit::*;
+pub use trace::*;
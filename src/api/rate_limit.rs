@@ -0,0 +1,49 @@
+//! Pull quotas reported by registries, kept per registry URL
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use crate::models::RateLimitQuota;
+use crate::utils::unix_now;
+
+/// Last quota seen per registry; `None` when manifest responses carry no quota headers
+fn quotas() -> &'static Mutex<HashMap<String, Option<RateLimitQuota>>> {
+    static QUOTAS: OnceLock<Mutex<HashMap<String, Option<RateLimitQuota>>>> = OnceLock::new();
+    QUOTAS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn quota_key(base_url: &str) -> String {
+    base_url.trim_end_matches('/').to_string()
+}
+
+/// Record the quota headers of a manifest response
+pub(crate) fn record_rate_limit(base_url: &str, headers: &reqwest::header::HeaderMap) {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let quota = match (header("ratelimit-limit"), header("ratelimit-remaining")) {
+        (Some(limit), Some(remaining)) => RateLimitQuota::parse(limit, remaining, unix_now()),
+        _ => None,
+    };
+    
+    if let Ok(mut quotas) = quotas().lock() {
+        // Error responses may omit the headers; keep the last known quota then
+        let entry = quotas.entry(quota_key(base_url)).or_insert(None);
+        if quota.is_some() {
+            *entry = quota;
+        }
+    }
+}
+
+/// Last quota reported by the registry at `base_url`
+pub fn rate_limit_quota(base_url: &str) -> Option<RateLimitQuota> {
+    quotas()
+        .lock()
+        .ok()
+        .and_then(|quotas| quotas.get(&quota_key(base_url)).cloned().flatten())
+}
+
+/// Whether a manifest response from `base_url` has been seen yet
+pub(crate) fn rate_limit_observed(base_url: &str) -> bool {
+    quotas()
+        .lock()
+        .map(|quotas| quotas.contains_key(&quota_key(base_url)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,149 @@
+//! Retry with exponential backoff for transient registry errors
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use rand::Rng;
+use crate::utils::{sleep, unix_now_millis};
+use super::ApiError;
+
+/// Delay before the first retry
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for a single backoff delay
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Longest `Retry-After` the client waits for on its own; longer waits fail with `RateLimited`
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+/// Exponential delay before retry number `attempt` (1-based), with jitter
+///
+/// The delay is picked at random between half and all of the exponential
+/// step so that parallel requests throttled together do not retry together.
+pub fn jittered_backoff(attempt: u32) -> Duration {
+    let exponential = BASE_DELAY
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_DELAY);
+    exponential.mul_f64(rand::rng().random_range(0.5..=1.0))
+}
+
+/// Delay before retry number `attempt` (1-based) after `error`
+///
+/// A rate limit waits at least as long as the registry asked for, capped at `MAX_DELAY`.
+pub fn backoff_delay(attempt: u32, error: &ApiError) -> Duration {
+    let delay = match error.kind() {
+        ApiError::RateLimited(seconds) => jittered_backoff(attempt).max(Duration::from_secs(*seconds)),
+        _ => jittered_backoff(attempt),
+    };
+    delay.min(MAX_DELAY)
+}
+
+/// Parse a `Retry-After` header given either as delay seconds or as an HTTP-date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    
+    // IMF-fixdate ("Wed, 21 Oct 2015 07:28:00 GMT") is a subset of RFC 2822
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.timestamp_millis() - chrono::Utc::now().timestamp_millis();
+    Some(Duration::from_millis(wait.max(0) as u64))
+}
+
+/// Run `op` until it succeeds, fails permanently or `max_attempts` is reached
+///
+/// Only 5xx and 429 responses are retried. Returns the last result together with
+/// the number of attempts made.
+pub async fn retry_transient<T, F, Fut>(max_attempts: u32, mut op: F) -> (Result<T, ApiError>, u32)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+{
+    let max_attempts = max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if e.is_transient() && attempt < max_attempts => {
+                sleep(backoff_delay(attempt, &e)).await;
+                attempt += 1;
+            }
+            result => return (result, attempt),
+        }
+    }
+}
+
+/// A request waiting to be retried, shown as a countdown in the UI
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryWait {
+    /// Registry base URL
+    pub registry: String,
+    /// HTTP status that caused the retry, `None` for network errors
+    pub status: Option<u16>,
+    /// Retry number about to be made (1-based)
+    pub attempt: u32,
+    /// Retries allowed for this registry
+    pub max_retries: u32,
+    /// Unix time in milliseconds when the retry is sent
+    pub retry_at: u64,
+}
+
+impl RetryWait {
+    /// Whole seconds left before the retry, rounded up
+    pub fn seconds_left(&self) -> u64 {
+        self.retry_at.saturating_sub(unix_now_millis()).div_ceil(1000)
+    }
+}
+
+fn pending_waits() -> &'static Mutex<HashMap<u64, RetryWait>> {
+    static PENDING_WAITS: OnceLock<Mutex<HashMap<u64, RetryWait>>> = OnceLock::new();
+    PENDING_WAITS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Requests currently waiting for a retry, soonest first
+pub fn retry_waits() -> Vec<RetryWait> {
+    let mut waits: Vec<RetryWait> = pending_waits()
+        .lock()
+        .map(|waits| waits.values().cloned().collect())
+        .unwrap_or_default();
+    waits.sort_by_key(|w| w.retry_at);
+    waits
+}
+
+/// Sleep for `delay`, listing the wait in `retry_waits` meanwhile
+pub(crate) async fn wait_for_retry(
+    registry: &str,
+    status: Option<u16>,
+    attempt: u32,
+    max_retries: u32,
+    delay: Duration,
+) {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    
+    if let Ok(mut waits) = pending_waits().lock() {
+        waits.insert(id, RetryWait {
+            registry: registry.to_string(),
+            status,
+            attempt,
+            max_retries,
+            retry_at: unix_now_millis() + delay.as_millis() as u64,
+        });
+    }
+    
+    // Removes the entry even when the request is dropped mid-wait
+    let _guard = WaitGuard(id);
+    sleep(delay).await;
+}
+
+struct WaitGuard(u64);
+
+impl Drop for WaitGuard {
+    fn drop(&mut self) {
+        if let Ok(mut waits) = pending_waits().lock() {
+            waits.remove(&self.0);
+        }
+    }
+}
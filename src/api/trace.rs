@@ -0,0 +1,129 @@
+//! Request/response traces of registry calls, kept in a bounded in-memory log
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use reqwest::header::HeaderMap;
+use crate::models::ApiRequestInfo;
+use crate::utils::{generate_curl_command_from_info, unix_now_millis};
+
+/// Traces kept in the network log; older ones are dropped first
+pub const MAX_TRACES: usize = 200;
+
+/// Response body bytes kept per trace
+pub const MAX_TRACE_BODY: usize = 4096;
+
+/// Headers whose values are never kept in a trace
+const SECRET_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie", "set-cookie"];
+
+static NEXT_TRACE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Bumped on every change so views can poll cheaply
+static LOG_REVISION: AtomicU64 = AtomicU64::new(0);
+
+fn traces() -> &'static Mutex<VecDeque<ApiRequestInfo>> {
+    static TRACES: OnceLock<Mutex<VecDeque<ApiRequestInfo>>> = OnceLock::new();
+    TRACES.get_or_init(|| Mutex::new(VecDeque::with_capacity(MAX_TRACES)))
+}
+
+/// Start a trace for a request that is about to be sent
+pub(crate) fn request_trace(request: &reqwest::Request) -> ApiRequestInfo {
+    let mut info = ApiRequestInfo {
+        id: NEXT_TRACE_ID.fetch_add(1, Ordering::Relaxed),
+        started_at: unix_now_millis(),
+        duration_ms: 0,
+        method: request.method().to_string(),
+        url: request.url().to_string(),
+        headers: redacted_headers(request.headers()),
+        status_code: None,
+        response_headers: None,
+        response_body: None,
+        error: None,
+        curl_command: String::new(),
+    };
+    info.curl_command = generate_curl_command_from_info(&info);
+    info
+}
+
+/// Complete a trace with the response and add it to the log
+pub(crate) fn finish_trace(info: &mut ApiRequestInfo, status: u16, headers: &HeaderMap, body: Option<&[u8]>) {
+    info.duration_ms = unix_now_millis().saturating_sub(info.started_at);
+    info.status_code = Some(status);
+    info.response_headers = Some(redacted_headers(headers));
+    info.response_body = body.filter(|b| !b.is_empty()).map(truncated_body);
+    record_trace(info.clone());
+}
+
+/// Complete a trace for a request that got no response and add it to the log
+pub(crate) fn fail_trace(info: &mut ApiRequestInfo, error: &str) {
+    info.duration_ms = unix_now_millis().saturating_sub(info.started_at);
+    info.error = Some(error.to_string());
+    record_trace(info.clone());
+}
+
+fn record_trace(info: ApiRequestInfo) {
+    if let Ok(mut traces) = traces().lock() {
+        if traces.len() >= MAX_TRACES {
+            traces.pop_front();
+        }
+        traces.push_back(info);
+    }
+    LOG_REVISION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Recorded traces, newest first
+pub fn network_log() -> Vec<ApiRequestInfo> {
+    traces()
+        .lock()
+        .map(|traces| traces.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Changes whenever a trace is recorded or the log is cleared
+pub fn network_log_revision() -> u64 {
+    LOG_REVISION.load(Ordering::Relaxed)
+}
+
+/// Remove every recorded trace
+pub fn clear_network_log() {
+    if let Ok(mut traces) = traces().lock() {
+        traces.clear();
+    }
+    LOG_REVISION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Header list with credentials masked, keeping the auth scheme
+fn redacted_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_string();
+            let value = value.to_str().unwrap_or("<binary>");
+            let value = if SECRET_HEADERS.contains(&name.as_str()) {
+                match value.split_once(' ') {
+                    Some((scheme, _)) if name.ends_with("authorization") => format!("{} ***", scheme),
+                    _ => "***".to_string(),
+                }
+            } else {
+                value.to_string()
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+/// Body as text, cut at `MAX_TRACE_BODY` bytes
+fn truncated_body(body: &[u8]) -> String {
+    let kept = &body[..body.len().min(MAX_TRACE_BODY)];
+    let text = match std::str::from_utf8(kept) {
+        Ok(text) => text.to_string(),
+        // The cut split a character
+        Err(e) if e.error_len().is_none() => String::from_utf8_lossy(&kept[..e.valid_up_to()]).into_owned(),
+        Err(_) => return format!("<{} bytes of binary data>", body.len()),
+    };
+    if kept.len() < body.len() {
+        format!("{}\n… ({} more bytes)", text, body.len() - kept.len())
+    } else {
+        text
+    }
+}
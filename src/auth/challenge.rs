@@ -66,6 +66,11 @@ pub fn parse_www_authenticate(header: &str) -> Option<AuthChallenge> {
 }
 
 impl AuthChallenge {
+    /// Check whether this is a Bearer (token server) challenge
+    pub fn is_bearer(&self) -> bool {
+        self.scheme.eq_ignore_ascii_case("bearer")
+    }
+    
     /// Get the realm parameter
     pub fn realm(&self) -> Option<&str> {
         self.params.get("realm").map(|s| s.as_str())
@@ -10,6 +10,7 @@ pub enum AuthType {
     Basic,
     Bearer,
     TlsCert,
+    IdentityToken,
 }
 
 /// Get the Authorization header value for the given auth config
@@ -23,6 +24,7 @@ pub fn get_auth_header(auth: &AuthConfig) -> Option<String> {
         }
         AuthConfig::BearerToken { token, .. } => Some(format!("Bearer {}", token)),
         AuthConfig::TlsCert { .. } => None, // TLS cert is handled at connection level
+        AuthConfig::IdentityToken { .. } => None, // Only sent to the token realm
     }
 }
 
@@ -44,5 +46,6 @@ pub fn get_auth_type(auth: &AuthConfig) -> AuthType {
         AuthConfig::BasicAuth { .. } => AuthType::Basic,
         AuthConfig::BearerToken { .. } => AuthType::Bearer,
         AuthConfig::TlsCert { .. } => AuthType::TlsCert,
+        AuthConfig::IdentityToken { .. } => AuthType::IdentityToken,
     }
 }
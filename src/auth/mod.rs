@@ -2,6 +2,8 @@
 
 mod handler;
 mod challenge;
+mod token;
 
 pub use handler::*;
 pub use challenge::*;
+pub use token::*;
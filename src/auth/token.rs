@@ -0,0 +1,122 @@
+//! Bearer token acquisition and caching for the Docker token auth flow
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use zeroize::Zeroize;
+
+/// Token lifetime assumed when the token server omits `expires_in` (per the distribution spec)
+const DEFAULT_TOKEN_LIFETIME: u64 = 60;
+
+/// Safety margin so tokens are refreshed slightly before they expire
+const EXPIRY_MARGIN: u64 = 5;
+
+/// Response body returned by a token server realm
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TokenResponse {
+    #[serde(default)]
+    pub token: Option<String>,
+    /// OAuth2-compatible alias of `token`
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// Lifetime in seconds
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub issued_at: Option<String>,
+}
+
+impl TokenResponse {
+    /// Get the bearer token, preferring `token` over `access_token`
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.token
+            .as_deref()
+            .or(self.access_token.as_deref())
+            .filter(|t| !t.is_empty())
+    }
+}
+
+/// Cached bearer token with its expiry time
+#[derive(Clone, Debug)]
+struct CachedToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+fn token_cache() -> &'static Mutex<HashMap<String, CachedToken>> {
+    static TOKEN_CACHE: OnceLock<Mutex<HashMap<String, CachedToken>>> = OnceLock::new();
+    TOKEN_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Build the cache key for a token (registry + identity + scope)
+pub fn token_cache_key(registry: &str, identity: &str, scope: &str) -> String {
+    format!("{}|{}|{}", registry, identity, scope)
+}
+
+/// Get a cached token if it has not expired yet
+pub fn get_cached_token(key: &str) -> Option<String> {
+    let mut cache = token_cache().lock().ok()?;
+    match cache.get(key) {
+        Some(cached) if cached.expires_at > Utc::now() => Some(cached.token.clone()),
+        Some(_) => {
+            cache.remove(key);
+            None
+        }
+        None => None,
+    }
+}
+
+/// Store a token in the cache until `expires_in` seconds from now
+pub fn cache_token(key: String, token: String, expires_in: Option<u64>) {
+    let lifetime = expires_in
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME)
+        .saturating_sub(EXPIRY_MARGIN)
+        .max(1);
+    let expires_at = Utc::now() + Duration::seconds(lifetime as i64);
+    if let Ok(mut cache) = token_cache().lock() {
+        cache.insert(key, CachedToken { token, expires_at });
+    }
+}
+
+/// Token endpoint a registry's bearer challenge pointed at
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenRealm {
+    pub realm: String,
+    pub service: Option<String>,
+}
+
+fn token_realms() -> &'static Mutex<HashMap<String, TokenRealm>> {
+    static TOKEN_REALMS: OnceLock<Mutex<HashMap<String, TokenRealm>>> = OnceLock::new();
+    TOKEN_REALMS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Remember the token endpoint of a registry for generated commands
+pub fn remember_token_realm(registry: &str, realm: TokenRealm) {
+    if let Ok(mut realms) = token_realms().lock() {
+        realms.insert(registry.trim_end_matches('/').to_string(), realm);
+    }
+}
+
+/// Token endpoint seen for a registry, if it challenged for a bearer token
+pub fn token_realm(registry: &str) -> Option<TokenRealm> {
+    token_realms().lock().ok()?.get(registry.trim_end_matches('/')).cloned()
+}
+
+/// Remove all cached tokens for a registry
+pub fn clear_cached_tokens(registry: &str) {
+    let prefix = format!("{}|", registry);
+    if let Ok(mut cache) = token_cache().lock() {
+        cache.retain(|key, _| !key.starts_with(&prefix));
+    }
+}
+
+/// Zeroize and remove every cached token
+pub fn clear_all_cached_tokens() {
+    if let Ok(mut cache) = token_cache().lock() {
+        for cached in cache.values_mut() {
+            cached.token.zeroize();
+        }
+        cache.clear();
+    }
+}
@@ -1,14 +1,19 @@
 //! Main App component
 
 use dioxus::prelude::*;
-use crate::state::AppState;
+use std::time::Duration;
+use crate::state::{use_retention_scheduler, AppState};
 use crate::models::Theme;
-use crate::storage::get_storage;
-use super::{RegistryList, RepositoryList, TagList, ManifestView, Toolbar, Settings};
+use crate::storage::{get_storage, CredentialBackend};
+use crate::utils::{sleep, unix_now_millis};
+use super::{RegistryList, RepositoryList, TagList, ManifestView, Toolbar, Settings, NetworkLog};
 
 /// Main application component
+///
+/// `on_lock` is called after the app state has dropped its secrets, either from
+/// the toolbar or when the idle auto-lock timeout expires.
 #[component]
-pub fn App() -> Element {
+pub fn App(on_lock: EventHandler<()>) -> Element {
     // Initialize app state and provide via context
     let app_state = use_context_provider(|| {
         let mut state = AppState::new();
@@ -28,11 +33,65 @@ pub fn App() -> Element {
             *state.cache_config.write() = cache_config;
         }
         
+        if let Ok(policies) = storage.load_retention_policies() {
+            *state.retention_policies.write() = policies;
+        }
+        
+        if let Ok(log) = storage.load_retention_log() {
+            *state.retention_log.write() = log;
+        }
+        
+        if let Ok(minutes) = storage.load_auto_lock_minutes() {
+            *state.auto_lock_minutes.write() = minutes;
+        }
+        
+        *state.credential_backend.write() = storage.credential_backend();
+        
         state
     });
     
+    // Saved retention policies run on their schedule while the app is open
+    use_retention_scheduler(app_state);
+    
     let theme = app_state.theme;
     let show_settings = use_signal(|| false);
+    let mut show_network_log = use_signal(|| false);
+    
+    // Locking while deletions run would cancel them without a report, so it waits
+    let mut lock_pending = use_signal(|| false);
+    let lock_now = move || {
+        let mut state = app_state;
+        state.lock();
+        on_lock.call(());
+    };
+    let lock = move |_| {
+        if !app_state.can_lock() {
+            return;
+        }
+        if app_state.is_busy() {
+            lock_pending.set(true);
+        } else {
+            lock_now();
+        }
+    };
+    
+    // Idle auto-lock; activity is only peeked so input events do not re-render
+    let mut last_activity = use_signal(unix_now_millis);
+    use_future(move || async move {
+        loop {
+            sleep(Duration::from_secs(5)).await;
+            if !app_state.can_lock() || app_state.is_busy() {
+                continue;
+            }
+            let minutes = *app_state.auto_lock_minutes.peek();
+            let idle_ms = unix_now_millis().saturating_sub(*last_activity.peek());
+            if *lock_pending.peek() || (minutes > 0 && idle_ms >= u64::from(minutes) * 60_000) {
+                lock_now();
+                break;
+            }
+        }
+    });
+    let mut touch = move || last_activity.set(unix_now_millis());
     
     // Get theme class
     let theme_class = match theme() {
@@ -45,6 +104,10 @@ pub fn App() -> Element {
         div {
             class: "app-layout",
             "data-theme": theme_class,
+            onmousemove: move |_| touch(),
+            onmousedown: move |_| touch(),
+            onkeydown: move |_| touch(),
+            onwheel: move |_| touch(),
             
             // Left sidebar - Registry list
                 aside {
@@ -57,7 +120,13 @@ pub fn App() -> Element {
                     class: "main-content",
                     
                     // Toolbar
-                    Toolbar { show_settings }
+                    Toolbar {
+                        show_settings,
+                        show_network_log,
+                        can_lock: (app_state.credential_backend)() == CredentialBackend::Encrypted,
+                        lock_pending: lock_pending(),
+                        on_lock: lock,
+                    }
                     
                     // Content area
                     section {
@@ -89,6 +158,11 @@ pub fn App() -> Element {
                             }
                         }
                     }
+                    
+                    // Request traces, docked below the content
+                    if show_network_log() {
+                        NetworkLog { on_close: move |_| show_network_log.set(false) }
+                    }
                 }
         }
     }
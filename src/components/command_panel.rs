@@ -0,0 +1,87 @@
+//! Copyable command lines for the current registry object
+
+use dioxus::prelude::*;
+use crate::models::RegistryConfig;
+use crate::utils::{copy_to_clipboard, generate_commands, CommandTarget, CredentialMode, ENV_PASSWORD, ENV_TOKEN, ENV_USERNAME};
+
+/// Collapsible list of curl, docker, skopeo, crane and oras commands for `targets`
+#[component]
+pub fn CommandPanel(registry: RegistryConfig, targets: Vec<CommandTarget>) -> Element {
+    let mut expanded = use_signal(|| false);
+    let mut from_env = use_signal(|| false);
+    let mut selected = use_signal(|| 0usize);
+    let mut copied = use_signal(|| None::<String>);
+    
+    let Some(target) = targets.get(selected().min(targets.len().saturating_sub(1))).cloned() else {
+        return rsx! {};
+    };
+    let mode = if from_env() { CredentialMode::Environment } else { CredentialMode::Masked };
+    let commands = generate_commands(&registry, &target, mode);
+    
+    rsx! {
+        div {
+            class: "manifest-section command-panel",
+            
+            h4 {
+                class: "command-panel-header",
+                onclick: move |_| expanded.set(!expanded()),
+                span { class: "run-toggle", if expanded() { "▼" } else { "▶" } }
+                "Commands"
+            }
+            
+            if expanded() {
+                if targets.len() > 1 {
+                    select {
+                        value: "{selected}",
+                        onchange: move |e| {
+                            if let Ok(index) = e.value().parse() {
+                                selected.set(index);
+                                copied.set(None);
+                            }
+                        },
+                        for (index, t) in targets.iter().enumerate() {
+                            option { value: "{index}", "{t.label()}" }
+                        }
+                    }
+                }
+                
+                label {
+                    class: "command-env",
+                    input {
+                        r#type: "checkbox",
+                        checked: from_env(),
+                        onchange: move |e| {
+                            from_env.set(e.checked());
+                            copied.set(None);
+                        },
+                    }
+                    "Fill credentials from ${ENV_USERNAME}, ${ENV_PASSWORD} and ${ENV_TOKEN}"
+                }
+                
+                for generated in commands {
+                    div {
+                        key: "{generated.tool.as_str()}",
+                        class: "command-item",
+                        span { class: "command-tool", "{generated.tool.as_str()}" }
+                        div {
+                            class: "curl-command",
+                            pre { "{generated.command}" }
+                            button {
+                                class: "btn-icon small",
+                                title: "Copy",
+                                onclick: {
+                                    let command = generated.command.clone();
+                                    move |_| {
+                                        copy_to_clipboard(&command);
+                                        copied.set(Some(command.clone()));
+                                    }
+                                },
+                                if copied().as_ref() == Some(&generated.command) { "✓" } else { "📋" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
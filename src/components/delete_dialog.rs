@@ -1,92 +1,97 @@
 //! Delete repository dialog component
 
+use std::time::Duration;
 use dioxus::prelude::*;
-use crate::api::RegistryClient;
-use crate::models::AuthConfig;
+use futures_util::stream::{self, StreamExt};
+use crate::api::{retry_transient, ApiError, RegistryClient};
+use crate::state::AppState;
+use crate::models::{DeletionEntry, DeletionResult, DeletionStatus, RegistryConfig};
+use crate::utils::{export_deletion_report_csv, export_deletion_report_json, sleep};
 
-/// Deletion result for tracking progress
-#[derive(Clone, Debug)]
-pub struct DeletionResult {
-    pub deleted: usize,
-    pub failed: usize,
-    pub errors: Vec<String>,
-}
+/// Number of tags deleted concurrently
+pub const DELETE_CONCURRENCY: usize = 4;
+
+/// How often paused workers check whether to resume
+const PAUSE_POLL: Duration = Duration::from_millis(200);
 
 /// Delete repository confirmation dialog
+///
+/// Tags are deleted by a bounded worker pool that can be paused and cancelled.
+/// Transient failures are retried with backoff; every tag ends up in the report.
 #[component]
 pub fn DeleteRepositoryDialog(
     repo_name: String,
     tags: Vec<String>,
-    registry_url: String,
-    registry_auth: AuthConfig,
+    registry: RegistryConfig,
     on_confirm: EventHandler<DeletionResult>,
     on_cancel: EventHandler<()>,
 ) -> Element {
     let mut deleting = use_signal(|| false);
-    let mut progress = use_signal(|| 0usize);
+    let mut paused = use_signal(|| false);
+    let mut cancelled = use_signal(|| false);
     let mut completed = use_signal(|| false);
-    let mut result = use_signal(|| DeletionResult {
-        deleted: 0,
-        failed: 0,
-        errors: Vec::new(),
-    });
+    let mut result = use_signal(DeletionResult::default);
+    let mut export_text = use_signal(String::new);
+    let mut app_state = use_context::<AppState>();
     
     let total_tags = tags.len();
     let has_tags = total_tags > 0;
     let tags_clone = tags.clone();
-    let url_clone = registry_url.clone();
-    let auth_clone = registry_auth.clone();
+    let registry_clone = registry.clone();
     let repo_clone = repo_name.clone();
     
     let start_deletion = move |_| {
         deleting.set(true);
         let tags_to_delete = tags_clone.clone();
-        let url = url_clone.clone();
-        let auth = auth_clone.clone();
+        let registry = registry_clone.clone();
         let repo = repo_clone.clone();
         
+        let running = app_state.begin_deletion();
         spawn(async move {
-            let mut deleted = 0usize;
-            let mut failed = 0usize;
-            let mut errors = Vec::new();
-            
-            match RegistryClient::new(url, auth) {
+            let _running = running;
+            match RegistryClient::for_registry(&registry) {
                 Ok(client) => {
-                    for (i, tag) in tags_to_delete.iter().enumerate() {
-                        match client.get_manifest(&repo, tag).await {
-                            Ok((_, digest)) => {
-                                if !digest.is_empty() {
-                                    match client.delete_manifest(&repo, &digest).await {
-                                        Ok(_) => deleted += 1,
-                                        Err(e) => {
-                                            failed += 1;
-                                            errors.push(format!("{}: {}", tag, e));
-                                        }
-                                    }
-                                } else {
-                                    failed += 1;
-                                    errors.push(format!("{}: No digest returned", tag));
-                                }
+                    let client = &client;
+                    let repo = repo.as_str();
+                    let mut entries = stream::iter(tags_to_delete)
+                        .map(|tag| async move {
+                            // Paused workers hold their slot until resumed or cancelled
+                            while *paused.peek() && !*cancelled.peek() {
+                                sleep(PAUSE_POLL).await;
                             }
-                            Err(e) => {
-                                failed += 1;
-                                errors.push(format!("{}: {}", tag, e));
+                            if *cancelled.peek() {
+                                return DeletionEntry::cancelled(tag);
                             }
-                        }
-                        progress.set(i + 1);
+                            delete_tag(client, repo, tag, cancelled).await
+                        })
+                        .buffer_unordered(DELETE_CONCURRENCY);
+                    
+                    while let Some(entry) = entries.next().await {
+                        result.write().entries.push(entry);
                     }
                 }
                 Err(e) => {
-                    errors.push(format!("Client error: {}", e));
-                    failed = tags_to_delete.len();
+                    result.set(DeletionResult {
+                        entries: tags_to_delete
+                            .into_iter()
+                            .map(|tag| DeletionEntry {
+                                tag,
+                                digest: String::new(),
+                                status: DeletionStatus::Failed,
+                                error: Some(format!("Client error: {}", e)),
+                                attempts: 0,
+                            })
+                            .collect(),
+                    });
                 }
             }
             
-            result.set(DeletionResult { deleted, failed, errors });
             completed.set(true);
         });
     };
     
+    let progress = result.read().entries.len();
+    
     rsx! {
         div {
             class: "modal-overlay",
@@ -106,25 +111,34 @@ pub fn DeleteRepositoryDialog(
                     // Deletion completed
                     div {
                         class: "delete-summary",
-                        p { "Repository deletion completed." }
+                        p {
+                            if result().cancelled() > 0 {
+                                "Repository deletion cancelled."
+                            } else {
+                                "Repository deletion completed."
+                            }
+                        }
                         p { 
                             "Deleted "
-                            strong { "{result().deleted}" }
+                            strong { "{result().deleted()}" }
                             " of {total_tags} tags."
                         }
+                        if result().cancelled() > 0 {
+                            p { "{result().cancelled()} tag(s) were not attempted." }
+                        }
                         
-                        if result().failed > 0 {
+                        if result().failed() > 0 {
                             div {
                                 class: "error-summary",
-                                p { class: "error", "Failed to delete {result().failed} tags:" }
+                                p { class: "error", "Failed to delete {result().failed()} tags:" }
                                 div {
                                     class: "error-list",
-                                    for err in result().errors.iter().take(5) {
+                                    for err in result().errors().into_iter().take(5) {
                                         p { class: "error-item", "{err}" }
                                     }
-                                    if result().errors.len() > 5 {
+                                    if result().failed() > 5 {
                                         {
-                                            let more = result().errors.len() - 5;
+                                            let more = result().failed() - 5;
                                             rsx! { p { class: "more-errors", "...and {more} more errors" } }
                                         }
                                     }
@@ -132,6 +146,28 @@ pub fn DeleteRepositoryDialog(
                             }
                         }
                         
+                        div {
+                            class: "report-export",
+                            button {
+                                class: "secondary small",
+                                onclick: move |_| export_text.set(export_deletion_report_json(&result.read())),
+                                "Export JSON"
+                            }
+                            button {
+                                class: "secondary small",
+                                onclick: move |_| export_text.set(export_deletion_report_csv(&result.read())),
+                                "Export CSV"
+                            }
+                        }
+                        if !export_text().is_empty() {
+                            textarea {
+                                class: "report-text",
+                                readonly: true,
+                                value: "{export_text}",
+                                rows: "8",
+                            }
+                        }
+                        
                         button {
                             class: "primary",
                             onclick: move |_| on_confirm.call(result()),
@@ -142,15 +178,39 @@ pub fn DeleteRepositoryDialog(
                     // Deletion in progress
                     div {
                         class: "delete-progress",
-                        p { "Deleting tags..." }
+                        p {
+                            if cancelled() {
+                                "Cancelling, waiting for running requests..."
+                            } else if paused() {
+                                "Paused"
+                            } else {
+                                "Deleting tags..."
+                            }
+                        }
                         div {
                             class: "progress-bar",
                             div {
                                 class: "progress-fill",
-                                style: "width: {(progress() * 100) / total_tags.max(1)}%",
+                                style: "width: {(progress * 100) / total_tags.max(1)}%",
+                            }
+                        }
+                        p { "{progress} / {total_tags}" }
+                        
+                        div {
+                            class: "form-actions",
+                            button {
+                                class: "secondary",
+                                disabled: cancelled(),
+                                onclick: move |_| paused.set(!paused()),
+                                if paused() { "Resume" } else { "Pause" }
+                            }
+                            button {
+                                class: "danger",
+                                disabled: cancelled(),
+                                onclick: move |_| cancelled.set(true),
+                                "Cancel"
                             }
                         }
-                        p { "{progress()} / {total_tags}" }
                     }
                 } else if !has_tags {
                     // No tags to delete
@@ -200,6 +260,46 @@ pub fn DeleteRepositoryDialog(
     }
 }
 
+/// Resolve a tag's digest and delete its manifest, retrying transient errors
+///
+/// The HEAD is already retried by the client; only the DELETE, which the client
+/// never retries, goes through `retry_transient` with the same limit. Cancels the
+/// remaining tags when the registry has deletion disabled.
+async fn delete_tag(client: &RegistryClient, repo: &str, tag: String, mut cancelled: Signal<bool>) -> DeletionEntry {
+    let digest = match client.head_manifest(repo, &tag).await {
+        Ok(info) if !info.digest.is_empty() => info.digest,
+        Ok(_) => return failed_entry(tag, String::new(), "No digest returned".to_string(), 0),
+        Err(e) => return failed_entry(tag, String::new(), e.to_string(), 0),
+    };
+    
+    let (delete, attempts) = retry_transient(client.max_attempts(), || client.delete_manifest(repo, &digest)).await;
+    match delete {
+        Err(e) if !e.is_not_found() => {
+            if matches!(e.kind(), ApiError::MethodNotAllowed(_)) {
+                cancelled.set(true);
+            }
+            failed_entry(tag, digest, e.to_string(), attempts)
+        }
+        // Ok, or another tag sharing this digest already removed the manifest
+        _ => DeletionEntry {
+            tag,
+            digest,
+            status: DeletionStatus::Deleted,
+            error: None,
+            attempts,
+        },
+    }
+}
+
+fn failed_entry(tag: String, digest: String, error: String, attempts: u32) -> DeletionEntry {
+    DeletionEntry {
+        tag,
+        digest,
+        status: DeletionStatus::Failed,
+        error: Some(error),
+        attempts,
+    }
+}
 
 /// Component shown when repository has no tags
 #[component]
@@ -1,6 +1,5 @@
 use dioxus::prelude::*;
-use crate::storage::{get_storage, init_key};
-use sha2::{Sha256, Digest};
+use crate::storage::get_storage;
 
 #[component]
 pub fn EncryptionPrompt(on_ready: EventHandler<()>) -> Element {
@@ -15,40 +14,28 @@ pub fn EncryptionPrompt(on_ready: EventHandler<()>) -> Element {
             return;
         }
 
-        // Hash password to get a 32-byte key
-        let mut hasher = Sha256::new();
-        hasher.update(pwd.as_bytes());
-        let result = hasher.finalize();
-        let mut key = [0u8; 32];
-        key.copy_from_slice(&result);
-
-        // Initialize storage with the key
-        if let Err(e) = init_key(key) {
-            // If already initialized, we might have reached here due to a race or reload
-            // In most cases, we just try to proceed or report error if it's a fixed failure
-            if !e.to_string().contains("already initialized") {
+        // Derive the key and verify it against the stored configuration
+        match get_storage().unlock(&pwd) {
+            Ok(()) => on_ready.call(()),
+            Err(e) if is_first_run => {
                 error.set(Some(format!("Encryption error: {}", e)));
-                return;
             }
-        }
-
-        // Verify if the key is correct if not first run
-        if !is_first_run {
-            match get_storage().load_registries() {
-                Ok(_) => on_ready.call(()),
-                Err(e) => {
-                    error.set(Some(format!("Incorrect password or corrupt configuration. Error: {}", e)));
-                }
+            Err(e) => {
+                error.set(Some(format!("Incorrect password or corrupt configuration. Error: {}", e)));
             }
-        } else {
-            on_ready.call(());
         }
     };
 
-    let mut handle_reset = move |_| {
+    let handle_reset = move |_| {
         let _ = get_storage().clear_all();
-        // Since OnceLock cannot be reset, we must inform the user to restart
-        error.set(Some("Configuration cleared. Please restart the application to set a new key.".to_string()));
+        password.set(String::new());
+        // The prompt re-renders in setup mode, the next password replaces the key
+        error.set(Some("Configuration cleared. Set a new password to start over.".to_string()));
+    };
+
+    let handle_keychain = move |_| match get_storage().use_keychain(&[]) {
+        Ok(()) => on_ready.call(()),
+        Err(e) => error.set(Some(format!("System keychain unavailable: {}", e))),
     };
 
     rsx! {
@@ -92,10 +79,17 @@ pub fn EncryptionPrompt(on_ready: EventHandler<()>) -> Element {
                         onclick: move |_| handle_submit(),
                         if is_first_run { "Set Key" } else { "Unlock" }
                     }
+                    if is_first_run {
+                        button {
+                            class: "secondary",
+                            onclick: handle_keychain,
+                            "Use System Keychain"
+                        }
+                    }
                     if !is_first_run && error().is_some() {
                         button {
                             class: "danger-button",
-                            onclick: move |evt| handle_reset(evt),
+                            onclick: handle_reset,
                             "Reset Configuration"
                         }
                     }
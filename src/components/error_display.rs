@@ -1,12 +1,18 @@
 //! Error display component
 
 use dioxus::prelude::*;
-use crate::models::{AppError, ErrorDetails};
+use crate::models::{AppError, ErrorDetails, RegistryError};
+use crate::utils::copy_to_clipboard;
 
 /// Error display component
 #[component]
 pub fn ErrorDisplay(error: AppError) -> Element {
     let mut show_details = use_signal(|| false);
+    let registry_errors = error
+        .details
+        .as_ref()
+        .map(|d| d.registry_errors.clone())
+        .unwrap_or_default();
     
     rsx! {
         div {
@@ -26,6 +32,15 @@ pub fn ErrorDisplay(error: AppError) -> Element {
                 }
             }
             
+            if !registry_errors.is_empty() {
+                div {
+                    class: "registry-errors",
+                    for (index, registry_error) in registry_errors.into_iter().enumerate() {
+                        RegistryErrorView { key: "{index}", error: registry_error }
+                    }
+                }
+            }
+            
             if show_details() {
                 if let Some(details) = &error.details {
                     ErrorDetailsView { details: details.clone() }
@@ -35,6 +50,29 @@ pub fn ErrorDisplay(error: AppError) -> Element {
     }
 }
 
+/// Single error reported by the registry, with its spec code
+#[component]
+fn RegistryErrorView(error: RegistryError) -> Element {
+    let detail = error
+        .detail
+        .as_ref()
+        .filter(|d| !d.is_null())
+        .and_then(|d| serde_json::to_string_pretty(d).ok());
+    
+    rsx! {
+        div {
+            class: "registry-error",
+            span { class: "registry-error-code", "{error.code.as_str()}" }
+            if !error.message.is_empty() {
+                span { class: "registry-error-message", "{error.message}" }
+            }
+            if let Some(detail) = detail {
+                pre { class: "detail-content", "{detail}" }
+            }
+        }
+    }
+}
+
 /// Error details view
 #[component]
 fn ErrorDetailsView(details: ErrorDetails) -> Element {
@@ -101,8 +139,9 @@ fn ErrorDetailsView(details: ErrorDetails) -> Element {
                         button {
                             class: "btn-icon small",
                             title: "Copy",
-                            onclick: move |_| {
-                                // Copy functionality would be implemented here
+                            onclick: {
+                                let curl = curl.clone();
+                                move |_| copy_to_clipboard(&curl)
                             },
                             "📋"
                         }
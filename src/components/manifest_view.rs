@@ -3,13 +3,15 @@
 use dioxus::prelude::*;
 use crate::state::AppState;
 use crate::api::RegistryClient;
-use crate::models::{Manifest, HistoryEntry};
-use crate::utils::{format_size, sorted_history_chronologically};
+use crate::components::{CommandPanel, ErrorDisplay};
+use crate::models::{AppError, Descriptor, HistoryEntry, ImageConfig, Manifest, PlatformManifest};
+use crate::utils::{format_size, sorted_history_chronologically, CommandTarget};
 
 /// Manifest details view component
 #[component]
 pub fn ManifestView() -> Element {
     let app_state = use_context::<AppState>();
+    let selected_repo = app_state.selected_repo.read().clone();
     let selected_tag = app_state.selected_tag.read().clone();
     
     let mut manifest = use_signal(|| None::<Manifest>);
@@ -17,7 +19,13 @@ pub fn ManifestView() -> Element {
     let mut raw_json = use_signal(String::new);
     let mut show_raw = use_signal(|| false);
     let mut loading = use_signal(|| false);
-    let mut error = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<AppError>);
+    
+    // Child manifest selected from a manifest list / image index
+    let mut platform_digest = use_signal(|| None::<String>);
+    let mut platform_manifest = use_signal(|| None::<Manifest>);
+    let mut platform_loading = use_signal(|| false);
+    let mut platform_error = use_signal(|| None::<AppError>);
     
     // Fetch manifest when tag changes
     let _fetch = use_resource(move || {
@@ -29,8 +37,10 @@ pub fn ManifestView() -> Element {
                 if let Some(registry) = app_state.get_registry(&id) {
                     loading.set(true);
                     error.set(None);
+                    platform_digest.set(None);
+                    platform_manifest.set(None);
                     
-                    match RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
+                    match RegistryClient::for_registry(&registry) {
                         Ok(client) => {
                             match client.get_manifest(&repo_name, &tag_name).await {
                                 Ok((m, d)) => {
@@ -43,13 +53,13 @@ pub fn ManifestView() -> Element {
                                     error.set(None);
                                 }
                                 Err(e) => {
-                                    error.set(Some(format!("Failed to fetch manifest: {}", e)));
+                                    error.set(Some(e.to_app_error()));
                                     manifest.set(None);
                                 }
                             }
                         }
                         Err(e) => {
-                            error.set(Some(format!("Failed to create client: {}", e)));
+                            error.set(Some(e.to_app_error()));
                             manifest.set(None);
                         }
                     }
@@ -65,6 +75,36 @@ pub fn ManifestView() -> Element {
         }
     });
     
+    // Drill into a platform-specific manifest
+    let mut select_platform = move |child_digest: String| {
+        let (Some(id), Some(repo_name)) = (
+            app_state.selected_registry.read().clone(),
+            app_state.selected_repo.read().clone(),
+        ) else {
+            return;
+        };
+        let Some(registry) = app_state.get_registry(&id) else {
+            return;
+        };
+        
+        platform_digest.set(Some(child_digest.clone()));
+        platform_manifest.set(None);
+        platform_error.set(None);
+        platform_loading.set(true);
+        
+        spawn(async move {
+            match RegistryClient::for_registry(&registry) {
+                Ok(client) => {
+                    match client.get_manifest(&repo_name, &child_digest).await {
+                        Ok((m, _)) => platform_manifest.set(Some(m)),
+                        Err(e) => platform_error.set(Some(e.to_app_error())),
+                    }
+                }
+                Err(e) => platform_error.set(Some(e.to_app_error())),
+            }
+            platform_loading.set(false);
+        });
+    };
     
     rsx! {
         div {
@@ -83,7 +123,7 @@ pub fn ManifestView() -> Element {
             } else if loading() {
                 p { class: "loading", "Loading manifest..." }
             } else if let Some(err) = error() {
-                p { class: "error", "{err}" }
+                ErrorDisplay { error: err }
             } else if let Some(m) = manifest() {
                 div {
                     class: "manifest-content",
@@ -107,21 +147,72 @@ pub fn ManifestView() -> Element {
                         }
                     }
                     
-                    // Layers
-                    div {
-                        class: "manifest-section",
-                        h4 { "Layers ({m.layers().len()})" }
+                    if let (Some(registry), Some(repo_name), Some(tag_name)) = (
+                        app_state.selected_registry.read().as_ref().and_then(|id| app_state.get_registry(id)),
+                        selected_repo.clone(),
+                        selected_tag.clone(),
+                    ) {
+                        CommandPanel {
+                            registry,
+                            targets: manifest_command_targets(&repo_name, &tag_name, &digest(), &m),
+                        }
+                    }
+                    
+                    if m.is_index() {
+                        // Platform matrix for multi-arch images
                         div {
-                            class: "layers-list",
-                            for (i, layer) in m.layers().iter().enumerate() {
-                                div {
-                                    key: "{layer.digest}",
-                                    class: "layer-item",
-                                    span { class: "layer-index", "{i + 1}" }
-                                    span { class: "layer-digest", title: "{layer.digest}", "{truncate_digest(&layer.digest)}" }
-                                    span { class: "layer-size", "{format_size(layer.size)}" }
+                            class: "manifest-section",
+                            h4 { "Platforms ({m.manifests().len()})" }
+                            div {
+                                class: "platform-list",
+                                for child in m.manifests().into_iter().cloned() {
+                                    PlatformItem {
+                                        key: "{child.digest}",
+                                        is_selected: platform_digest().as_ref() == Some(&child.digest),
+                                        child,
+                                        on_select: move |d: String| select_platform(d),
+                                    }
+                                }
+                            }
+                        }
+                        
+                        if platform_loading() {
+                            p { class: "loading", "Loading platform manifest..." }
+                        } else if let Some(err) = platform_error() {
+                            ErrorDisplay { error: err }
+                        } else if let Some(child) = platform_manifest() {
+                            div {
+                                class: "manifest-section",
+                                h4 { "Platform Manifest" }
+                                dl {
+                                    dt { "Digest" }
+                                    dd {
+                                        class: "digest-value",
+                                        "{platform_digest().unwrap_or_default()}"
+                                    }
+                                    dt { "Media Type" }
+                                    dd { "{child.media_type()}" }
+                                    dt { "Total Size" }
+                                    dd { "{format_size(child.total_size())}" }
                                 }
                             }
+                            LayersSection { manifest: child.clone() }
+                            if let Some(config) = child.config() {
+                                ImageConfigSection {
+                                    key: "{config.digest}",
+                                    repo: selected_repo.clone().unwrap_or_default(),
+                                    config: config.clone(),
+                                }
+                            }
+                        }
+                    } else {
+                        LayersSection { manifest: m.clone() }
+                        if let Some(config) = m.config() {
+                            ImageConfigSection {
+                                key: "{config.digest}",
+                                repo: selected_repo.clone().unwrap_or_default(),
+                                config: config.clone(),
+                            }
                         }
                     }
                     
@@ -147,6 +238,191 @@ pub fn ManifestView() -> Element {
     }
 }
 
+/// Layer list of a single-platform manifest
+#[component]
+fn LayersSection(manifest: Manifest) -> Element {
+    rsx! {
+        div {
+            class: "manifest-section",
+            h4 { "Layers ({manifest.layers().len()})" }
+            div {
+                class: "layers-list",
+                for (i, layer) in manifest.layers().iter().enumerate() {
+                    div {
+                        key: "{layer.digest}",
+                        class: "layer-item",
+                        span { class: "layer-index", "{i + 1}" }
+                        span { class: "layer-digest", title: "{layer.digest}", "{truncate_digest(&layer.digest)}" }
+                        span { class: "layer-size", "{format_size(layer.size)}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Image configuration fetched from the config blob
+#[component]
+fn ImageConfigSection(repo: String, config: Descriptor) -> Element {
+    let app_state = use_context::<AppState>();
+    
+    let image_config = use_resource(move || {
+        let repo = repo.clone();
+        let config = config.clone();
+        async move {
+            let id = app_state.selected_registry.peek().clone().ok_or("No registry selected")?;
+            let registry = app_state.get_registry(&id).ok_or("Registry not found")?;
+            let client = RegistryClient::for_registry(&registry)
+                .map_err(|e| format!("Failed to create client: {}", e))?;
+            client
+                .get_config(&repo, &config)
+                .await
+                .map_err(|e| format!("Failed to fetch image config: {}", e))
+        }
+    });
+    
+    let result = image_config.read().clone();
+    
+    rsx! {
+        match result {
+            None => rsx! { p { class: "loading", "Loading image config..." } },
+            Some(Err(err)) => rsx! { p { class: "error", "{err}" } },
+            Some(Ok(image)) => rsx! { ImageConfigDetails { image } },
+        }
+    }
+}
+
+/// Sections rendered from an image config
+#[component]
+fn ImageConfigDetails(image: ImageConfig) -> Element {
+    let runtime = image.config.clone().unwrap_or_default();
+    let env = runtime.env.unwrap_or_default();
+    let labels = runtime.labels.unwrap_or_default();
+    let ports: Vec<String> = runtime.exposed_ports.unwrap_or_default().into_keys().collect();
+    let entrypoint = runtime.entrypoint.map(|v| v.join(" "));
+    let cmd = runtime.cmd.map(|v| v.join(" "));
+    let diff_ids = image.rootfs.as_ref().map(|r| r.diff_ids.clone()).unwrap_or_default();
+    let history = image.history.clone().unwrap_or_default();
+    
+    rsx! {
+        div {
+            class: "manifest-section",
+            h4 { "Image Config" }
+            dl {
+                dt { "Platform" }
+                dd { "{image.os}/{image.architecture}" }
+                if let Some(created) = &image.created {
+                    dt { "Created" }
+                    dd { "{created}" }
+                }
+                if let Some(author) = &image.author {
+                    dt { "Author" }
+                    dd { "{author}" }
+                }
+                if let Some(entrypoint) = entrypoint {
+                    dt { "Entrypoint" }
+                    dd { class: "config-value", "{entrypoint}" }
+                }
+                if let Some(cmd) = cmd {
+                    dt { "Cmd" }
+                    dd { class: "config-value", "{cmd}" }
+                }
+                if let Some(dir) = runtime.working_dir.filter(|d| !d.is_empty()) {
+                    dt { "Working Dir" }
+                    dd { class: "config-value", "{dir}" }
+                }
+                if let Some(user) = runtime.user.filter(|u| !u.is_empty()) {
+                    dt { "User" }
+                    dd { class: "config-value", "{user}" }
+                }
+                if !ports.is_empty() {
+                    dt { "Exposed Ports" }
+                    dd { class: "config-value", "{ports.join(\", \")}" }
+                }
+            }
+        }
+        
+        if !env.is_empty() {
+            div {
+                class: "manifest-section",
+                h4 { "Environment ({env.len()})" }
+                pre {
+                    class: "config-list",
+                    for var in env.iter() {
+                        "{var}\n"
+                    }
+                }
+            }
+        }
+        
+        if !labels.is_empty() {
+            div {
+                class: "manifest-section",
+                h4 { "Labels ({labels.len()})" }
+                dl {
+                    class: "labels-list",
+                    for (key, value) in labels.iter() {
+                        dt { "{key}" }
+                        dd { class: "config-value", "{value}" }
+                    }
+                }
+            }
+        }
+        
+        if !diff_ids.is_empty() {
+            div {
+                class: "manifest-section",
+                h4 { "RootFS Diff IDs ({diff_ids.len()})" }
+                div {
+                    class: "layers-list",
+                    for (i, diff_id) in diff_ids.iter().enumerate() {
+                        div {
+                            key: "{i}",
+                            class: "layer-item",
+                            span { class: "layer-index", "{i + 1}" }
+                            span { class: "layer-digest", title: "{diff_id}", "{truncate_digest(diff_id)}" }
+                        }
+                    }
+                }
+            }
+        }
+        
+        div {
+            class: "manifest-section",
+            HistoryView { history }
+        }
+    }
+}
+
+/// Single platform entry of a manifest list / image index
+#[component]
+fn PlatformItem(
+    child: PlatformManifest,
+    is_selected: bool,
+    on_select: EventHandler<String>,
+) -> Element {
+    let platform = child.platform
+        .as_ref()
+        .map(|p| p.display_name())
+        .unwrap_or_else(|| "unknown".to_string());
+    let os_version = child.platform.as_ref().and_then(|p| p.os_version.clone());
+    let digest = child.digest.clone();
+    
+    rsx! {
+        div {
+            class: if is_selected { "platform-item selected" } else { "platform-item" },
+            onclick: move |_| on_select.call(digest.clone()),
+            
+            span { class: "platform-name", "{platform}" }
+            if let Some(version) = os_version {
+                span { class: "platform-os-version", "{version}" }
+            }
+            span { class: "layer-digest", title: "{child.digest}", "{truncate_digest(&child.digest)}" }
+            span { class: "layer-size", "{format_size(child.size)}" }
+        }
+    }
+}
+
 /// Truncate digest for display
 fn truncate_digest(digest: &str) -> String {
     if digest.len() > 24 {
@@ -194,3 +470,30 @@ pub fn HistoryView(history: Vec<HistoryEntry>) -> Element {
         }
     }
 }
+
+/// Commands offered for a manifest: by tag, by digest, its config blob and deletion
+fn manifest_command_targets(repo: &str, tag: &str, digest: &str, manifest: &Manifest) -> Vec<CommandTarget> {
+    let mut targets = vec![CommandTarget::Manifest {
+        repo: repo.to_string(),
+        reference: tag.to_string(),
+    }];
+    if !digest.is_empty() {
+        targets.push(CommandTarget::Manifest {
+            repo: repo.to_string(),
+            reference: digest.to_string(),
+        });
+    }
+    if let Some(config) = manifest.config() {
+        targets.push(CommandTarget::Blob {
+            repo: repo.to_string(),
+            digest: config.digest.clone(),
+        });
+    }
+    if !digest.is_empty() {
+        targets.push(CommandTarget::Delete {
+            repo: repo.to_string(),
+            digest: digest.to_string(),
+        });
+    }
+    targets
+}
@@ -11,6 +11,10 @@ mod settings;
 mod error_display;
 mod delete_dialog;
 mod encryption_prompt;
+mod retention_dialog;
+mod retention_policies;
+mod network_log;
+mod command_panel;
 
 pub use app::*;
 pub use registry_list::*;
@@ -23,3 +27,7 @@ pub use settings::*;
 pub use error_display::*;
 pub use encryption_prompt::*;
 pub use delete_dialog::*;
+pub use retention_dialog::*;
+pub use retention_policies::*;
+pub use network_log::*;
+pub use command_panel::*;
@@ -0,0 +1,178 @@
+//! Network log panel listing recent registry requests
+
+use std::time::Duration;
+use dioxus::prelude::*;
+use crate::api::{clear_network_log, network_log, network_log_revision, MAX_TRACES};
+use crate::models::ApiRequestInfo;
+use crate::utils::{copy_to_clipboard, sleep};
+
+/// Panel with the traces of recent registry requests, newest first
+#[component]
+pub fn NetworkLog(on_close: EventHandler<()>) -> Element {
+    let mut traces = use_signal(network_log);
+    let mut filter = use_signal(String::new);
+    let mut errors_only = use_signal(|| false);
+    
+    // Traces are recorded by the API client, outside of the app state
+    use_future(move || async move {
+        let mut seen = network_log_revision();
+        loop {
+            sleep(Duration::from_millis(500)).await;
+            let revision = network_log_revision();
+            if revision != seen {
+                seen = revision;
+                traces.set(network_log());
+            }
+        }
+    });
+    
+    let query = filter().to_lowercase();
+    let visible: Vec<ApiRequestInfo> = traces()
+        .into_iter()
+        .filter(|t| !errors_only() || t.error.is_some() || t.status_code.is_some_and(|s| s >= 400))
+        .filter(|t| query.is_empty() || t.url.to_lowercase().contains(&query))
+        .collect();
+    
+    rsx! {
+        div {
+            class: "network-log",
+            
+            div {
+                class: "panel-header",
+                h3 { "Network Log" }
+                input {
+                    r#type: "text",
+                    class: "network-filter",
+                    placeholder: "Filter by URL...",
+                    value: "{filter}",
+                    oninput: move |e| filter.set(e.value()),
+                }
+                label {
+                    class: "network-errors-only",
+                    input {
+                        r#type: "checkbox",
+                        checked: errors_only(),
+                        onchange: move |e| errors_only.set(e.checked()),
+                    }
+                    "Errors only"
+                }
+                button {
+                    class: "secondary small",
+                    onclick: move |_| {
+                        clear_network_log();
+                        traces.set(Vec::new());
+                    },
+                    "Clear"
+                }
+                button {
+                    class: "btn-icon small",
+                    title: "Close",
+                    onclick: move |_| on_close.call(()),
+                    "×"
+                }
+            }
+            
+            p { class: "hint", "Last {MAX_TRACES} requests. Credentials are masked and response bodies truncated." }
+            
+            if visible.is_empty() {
+                p { class: "empty-message", "No requests recorded" }
+            } else {
+                div {
+                    class: "network-entries",
+                    for trace in visible {
+                        NetworkLogEntry { key: "{trace.id}", trace }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Single request, expandable to headers, body and a cURL reproduction
+#[component]
+fn NetworkLogEntry(trace: ApiRequestInfo) -> Element {
+    let mut expanded = use_signal(|| false);
+    let curl = trace.curl_command.clone();
+    
+    let (status_class, status_label) = match trace.status_code {
+        Some(status) if status >= 400 => ("network-status error", status.to_string()),
+        Some(status) => ("network-status", status.to_string()),
+        None => ("network-status error", "failed".to_string()),
+    };
+    let time = chrono::DateTime::from_timestamp_millis(trace.started_at as i64)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+    
+    rsx! {
+        div {
+            class: "network-entry",
+            
+            div {
+                class: "network-summary",
+                onclick: move |_| expanded.set(!expanded()),
+                span { class: "network-time", "{time}" }
+                span { class: "network-method", "{trace.method}" }
+                span { class: "{status_class}", "{status_label}" }
+                span { class: "network-url", title: "{trace.url}", "{trace.url}" }
+                span { class: "network-duration", "{trace.duration_ms} ms" }
+            }
+            
+            if expanded() {
+                div {
+                    class: "error-details",
+                    
+                    if let Some(error) = &trace.error {
+                        p { class: "error-item", "{error}" }
+                    }
+                    
+                    div {
+                        class: "detail-section",
+                        span { class: "detail-label", "Request Headers" }
+                        pre {
+                            class: "detail-content",
+                            for (key, value) in trace.headers.iter() {
+                                "{key}: {value}\n"
+                            }
+                        }
+                    }
+                    
+                    if let Some(headers) = &trace.response_headers {
+                        div {
+                            class: "detail-section",
+                            span { class: "detail-label", "Response Headers" }
+                            pre {
+                                class: "detail-content",
+                                for (key, value) in headers.iter() {
+                                    "{key}: {value}\n"
+                                }
+                            }
+                        }
+                    }
+                    
+                    if let Some(body) = &trace.response_body {
+                        div {
+                            class: "detail-section",
+                            span { class: "detail-label", "Response Body" }
+                            pre { class: "detail-content", "{body}" }
+                        }
+                    }
+                    
+                    div {
+                        class: "detail-section",
+                        span { class: "detail-label", "cURL Command:" }
+                        div {
+                            class: "curl-command",
+                            pre { "{trace.curl_command}" }
+                            button {
+                                class: "btn-icon small",
+                                title: "Copy",
+                                onclick: move |_| copy_to_clipboard(&curl),
+                                "📋"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
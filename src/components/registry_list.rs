@@ -1,8 +1,12 @@
 //! Registry list component
 
+use std::collections::HashMap;
+use std::time::Duration;
 use dioxus::prelude::*;
 use crate::state::AppState;
-use crate::models::{AuthConfig, ConnectionStatus, RegistryConfig};
+use crate::api::rate_limit_quota;
+use crate::models::{AuthConfig, ConnectionStatus, DeleteSupport, RateLimitQuota, RegistryConfig, TlsOptions, DEFAULT_MAX_RETRIES};
+use crate::utils::sleep;
 
 /// Registry list sidebar component
 #[component]
@@ -11,6 +15,23 @@ pub fn RegistryList() -> Element {
     let mut show_form = use_signal(|| false);
     let mut editing_id = use_signal(|| None::<String>);
     let mut delete_confirm_id = use_signal(|| None::<String>);
+    let mut quotas = use_signal(HashMap::<String, RateLimitQuota>::new);
+    
+    // Quotas are captured by the API client from manifest responses
+    use_future(move || async move {
+        loop {
+            let current: HashMap<String, RateLimitQuota> = app_state
+                .registries
+                .peek()
+                .iter()
+                .filter_map(|r| rate_limit_quota(&r.url).map(|q| (r.id.clone(), q)))
+                .collect();
+            if *quotas.peek() != current {
+                quotas.set(current);
+            }
+            sleep(Duration::from_secs(2)).await;
+        }
+    });
     
     let registries = app_state.registries.read().clone();
     let selected = app_state.selected_registry.read().clone();
@@ -50,6 +71,8 @@ pub fn RegistryList() -> Element {
                 RegistryItem {
                     registry: registry.clone(),
                     is_selected: selected.as_ref() == Some(&registry.id),
+                    delete_support: app_state.get_delete_support(&registry.id),
+                    quota: quotas.read().get(&registry.id).cloned(),
                     on_select: move |id: String| {
                         app_state.select_registry(Some(id));
                     },
@@ -132,6 +155,8 @@ pub fn RegistryList() -> Element {
 fn RegistryItem(
     registry: RegistryConfig,
     is_selected: bool,
+    delete_support: DeleteSupport,
+    quota: Option<RateLimitQuota>,
     on_select: EventHandler<String>,
     on_edit: EventHandler<String>,
     on_delete: EventHandler<String>,
@@ -160,6 +185,30 @@ fn RegistryItem(
                 class: "registry-info",
                 span { class: "registry-name", "{registry.name}" }
                 span { class: "registry-url", "{registry.url}" }
+                if registry.tls.insecure_skip_verify {
+                    span {
+                        class: "registry-warning",
+                        title: "Server certificate verification is disabled for this registry",
+                        "⚠️ TLS verification off"
+                    }
+                }
+                if let Some(quota) = quota {
+                    span {
+                        class: if quota.is_low() { "registry-quota low" } else { "registry-quota" },
+                        title: "Pull quota reported by the registry, shared by every client using these credentials",
+                        "⛽ {quota.remaining}/{quota.limit} pulls left"
+                        if let Some(window) = quota.window_label() {
+                            " ({window} window)"
+                        }
+                    }
+                }
+                if let Some(reason) = delete_support.explanation() {
+                    span {
+                        class: "registry-readonly",
+                        title: "{reason}",
+                        "🔒 Read-only"
+                    }
+                }
             }
             
             div {
@@ -205,6 +254,7 @@ fn RegistryFormModal(
             AuthConfig::BasicAuth { .. } => "basic",
             AuthConfig::BearerToken { .. } => "bearer",
             AuthConfig::TlsCert { .. } => "tls",
+            AuthConfig::IdentityToken { .. } => "identity",
         }).unwrap_or("anonymous").to_string()
     });
     let mut username = use_signal(|| {
@@ -215,6 +265,22 @@ fn RegistryFormModal(
     });
     let mut password = use_signal(String::new);
     let mut token = use_signal(String::new);
+    let mut cert_path = use_signal(|| {
+        existing.as_ref().and_then(|r| match &r.auth {
+            AuthConfig::TlsCert { cert_path, .. } => Some(cert_path.clone()),
+            _ => None,
+        }).unwrap_or_default()
+    });
+    let mut key_path = use_signal(|| {
+        existing.as_ref().and_then(|r| match &r.auth {
+            AuthConfig::TlsCert { key_path, .. } => Some(key_path.clone()),
+            _ => None,
+        }).unwrap_or_default()
+    });
+    
+    let tls = use_signal(|| existing.as_ref().map(|r| r.tls.clone()).unwrap_or_default());
+    let mut max_retries = use_signal(|| existing.as_ref().map(|r| r.max_retries).unwrap_or(DEFAULT_MAX_RETRIES));
+    let mut form_error = use_signal(|| None::<String>);
     
     let title = if editing_id.is_some() { "Edit Registry" } else { "Add Registry" };
     
@@ -233,6 +299,11 @@ fn RegistryFormModal(
                     onsubmit: move |e| {
                         e.prevent_default();
                         
+                        if url().trim().starts_with("http://") && !tls().allow_http {
+                            form_error.set(Some("Enable \"Allow plain HTTP\" to use an http:// URL.".to_string()));
+                            return;
+                        }
+                        
                         let auth = match auth_type().as_str() {
                             "basic" => AuthConfig::BasicAuth {
                                 username: username(),
@@ -243,6 +314,14 @@ fn RegistryFormModal(
                                 token: token(),
                                 encrypted_token: String::new(),
                             },
+                            "tls" => AuthConfig::TlsCert {
+                                cert_path: cert_path(),
+                                key_path: key_path(),
+                            },
+                            "identity" => AuthConfig::IdentityToken {
+                                token: token(),
+                                encrypted_token: String::new(),
+                            },
                             _ => AuthConfig::Anonymous,
                         };
                         
@@ -252,10 +331,16 @@ fn RegistryFormModal(
                                 name: name(),
                                 url: url(),
                                 auth,
+                                tls: tls(),
+                                max_retries: max_retries(),
                                 status: ConnectionStatus::Unknown,
                             }
                         } else {
-                            RegistryConfig::new(name(), url(), auth)
+                            RegistryConfig {
+                                tls: tls(),
+                                max_retries: max_retries(),
+                                ..RegistryConfig::new(name(), url(), auth)
+                            }
                         };
                         
                         on_save.call(config);
@@ -279,7 +364,10 @@ fn RegistryFormModal(
                             r#type: "url",
                             value: "{url}",
                             placeholder: "https://registry.example.com",
-                            oninput: move |e| url.set(e.value()),
+                            oninput: move |e| {
+                                url.set(e.value());
+                                form_error.set(None);
+                            },
                             required: true,
                         }
                     }
@@ -293,6 +381,10 @@ fn RegistryFormModal(
                             option { value: "anonymous", "Anonymous" }
                             option { value: "basic", "Basic Auth" }
                             option { value: "bearer", "Bearer Token" }
+                            option { value: "identity", "Identity Token" }
+                            if cfg!(not(target_arch = "wasm32")) {
+                                option { value: "tls", "TLS Client Certificate" }
+                            }
                         }
                     }
                     
@@ -317,7 +409,7 @@ fn RegistryFormModal(
                         }
                     }
                     
-                    if auth_type() == "bearer" {
+                    if auth_type() == "bearer" || auth_type() == "identity" {
                         div {
                             class: "form-group",
                             label { "Token" }
@@ -326,9 +418,58 @@ fn RegistryFormModal(
                                 value: "{token}",
                                 oninput: move |e| token.set(e.value()),
                             }
+                            if auth_type() == "identity" {
+                                p { class: "hint", "OAuth2 refresh token, as stored by `docker login` for Docker Hub and other token services." }
+                            }
                         }
                     }
                     
+                    if auth_type() == "tls" {
+                        div {
+                            class: "form-group",
+                            label { "Certificate (PEM) Path" }
+                            input {
+                                r#type: "text",
+                                value: "{cert_path}",
+                                placeholder: "/path/to/client.crt",
+                                oninput: move |e| cert_path.set(e.value()),
+                            }
+                        }
+                        div {
+                            class: "form-group",
+                            label { "Private Key (PEM) Path" }
+                            input {
+                                r#type: "text",
+                                value: "{key_path}",
+                                placeholder: "/path/to/client.key",
+                                oninput: move |e| key_path.set(e.value()),
+                            }
+                        }
+                    }
+                    
+                    TlsOptionsFields { tls }
+                    
+                    div {
+                        class: "form-group",
+                        label { "Automatic Retries" }
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            max: "10",
+                            value: "{max_retries}",
+                            oninput: move |e| {
+                                if let Ok(retries) = e.value().parse::<u32>() {
+                                    max_retries.set(retries.min(10));
+                                }
+                            },
+                        }
+                        p { class: "hint", "Throttled or failed reads are retried with backoff, honoring Retry-After. 0 disables retries." }
+                    }
+                    
+                    if let Some(err) = form_error() {
+                        p { class: "error", "{err}" }
+                    }
+                    
                     div {
                         class: "form-actions",
                         button {
@@ -348,3 +489,54 @@ fn RegistryFormModal(
         }
     }
 }
+
+/// Connection security options shared by the add/edit form
+#[component]
+fn TlsOptionsFields(tls: Signal<TlsOptions>) -> Element {
+    let options = tls();
+    
+    rsx! {
+        if cfg!(not(target_arch = "wasm32")) {
+            div {
+                class: "form-group",
+                label { "Extra CA Bundle (PEM) Path" }
+                input {
+                    r#type: "text",
+                    value: "{options.ca_bundle_path}",
+                    placeholder: "/path/to/corporate-ca.pem",
+                    oninput: move |e| tls.write().ca_bundle_path = e.value(),
+                }
+            }
+            
+            div {
+                class: "form-group checkbox",
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: options.insecure_skip_verify,
+                        onchange: move |e| tls.write().insecure_skip_verify = e.checked(),
+                    }
+                    "Skip TLS certificate verification"
+                }
+                if options.insecure_skip_verify {
+                    p { class: "form-warning", "⚠️ Insecure: the registry's identity will not be verified and credentials can be intercepted." }
+                }
+            }
+        }
+        
+        div {
+            class: "form-group checkbox",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: options.allow_http,
+                    onchange: move |e| tls.write().allow_http = e.checked(),
+                }
+                "Allow plain HTTP"
+            }
+            if options.allow_http {
+                p { class: "form-warning", "⚠️ Credentials are sent unencrypted over HTTP." }
+            }
+        }
+    }
+}
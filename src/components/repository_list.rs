@@ -2,9 +2,10 @@
 
 use dioxus::prelude::*;
 use crate::state::AppState;
-use crate::api::RegistryClient;
-use crate::models::AuthConfig;
-use crate::components::delete_dialog::{DeleteRepositoryDialog, DeletionResult};
+use crate::api::{ApiError, RegistryClient};
+use crate::models::{AppError, DeleteSupport, DeletionResult, RegistryConfig};
+use crate::components::delete_dialog::DeleteRepositoryDialog;
+use crate::components::ErrorDisplay;
 use crate::utils::{filter_strings_owned, sorted_alphabetically};
 
 /// Repository list component
@@ -17,19 +18,21 @@ pub fn RepositoryList() -> Element {
     let mut search = use_signal(String::new);
     let mut repositories = use_signal(Vec::<String>::new);
     let mut loading = use_signal(|| false);
-    let mut error = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<AppError>);
     let mut delete_status = use_signal(|| None::<String>);
     
     // Delete dialog state
     let mut show_delete_dialog = use_signal(|| false);
     let mut delete_repo_name = use_signal(String::new);
     let mut delete_tags = use_signal(Vec::<String>::new);
-    let mut delete_registry_url = use_signal(String::new);
-    let mut delete_registry_auth = use_signal(|| AuthConfig::Anonymous);
+    let mut delete_registry = use_signal(|| None::<RegistryConfig>);
     
     // Get the selected registry config
     let selected_registry = selected_registry_id.as_ref()
         .and_then(|id| app_state.get_registry(id));
+    let delete_support = selected_registry_id.as_ref()
+        .map(|id| app_state.get_delete_support(id))
+        .unwrap_or_default();
     
     // Fetch repositories when registry changes
     let _fetch = use_resource(move || {
@@ -39,22 +42,18 @@ pub fn RepositoryList() -> Element {
                 if let Some(registry) = app_state.get_registry(&id) {
                     loading.set(true);
                     error.set(None);
+                    repositories.set(Vec::new());
                     
-                    match RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
-                        Ok(client) => {
-                            match client.get_catalog(None).await {
-                                Ok(catalog) => {
-                                    repositories.set(catalog.repositories);
-                                    error.set(None);
-                                }
-                                Err(e) => {
-                                    error.set(Some(format!("Failed to fetch repositories: {}", e)));
-                                    repositories.set(Vec::new());
-                                }
+                    let page_size = app_state.cache_config.peek().page_size;
+                    match load_repositories(registry.clone(), page_size, repositories).await {
+                        Ok(()) => {
+                            let first_repo = repositories.peek().first().cloned();
+                            if let Some(repo) = first_repo {
+                                probe_delete_support(registry, repo, app_state).await;
                             }
                         }
                         Err(e) => {
-                            error.set(Some(format!("Failed to create client: {}", e)));
+                            error.set(Some(e.to_app_error()));
                             repositories.set(Vec::new());
                         }
                     }
@@ -83,22 +82,10 @@ pub fn RepositoryList() -> Element {
                 loading.set(true);
                 error.set(None);
                 
+                let page_size = app_state.cache_config.peek().page_size;
                 spawn(async move {
-                    match RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
-                        Ok(client) => {
-                            match client.get_catalog(None).await {
-                                Ok(catalog) => {
-                                    repositories.set(catalog.repositories);
-                                    error.set(None);
-                                }
-                                Err(e) => {
-                                    error.set(Some(format!("Failed to fetch: {}", e)));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            error.set(Some(format!("Client error: {}", e)));
-                        }
+                    if let Err(e) = load_repositories(registry, page_size, repositories).await {
+                        error.set(Some(e.to_app_error()));
                     }
                     loading.set(false);
                 });
@@ -111,21 +98,19 @@ pub fn RepositoryList() -> Element {
         if let Some(id) = app_state.selected_registry.read().clone() {
             if let Some(registry) = app_state.get_registry(&id) {
                 let repo_clone = repo.clone();
-                let url = registry.url.clone();
-                let auth = registry.auth.clone();
                 
                 // Store registry info for dialog
                 delete_repo_name.set(repo);
-                delete_registry_url.set(url.clone());
-                delete_registry_auth.set(auth.clone());
+                delete_registry.set(Some(registry.clone()));
                 
                 // Fetch tags for the repository
+                let page_size = app_state.cache_config.peek().page_size;
                 spawn(async move {
-                    match RegistryClient::new(url, auth) {
+                    match RegistryClient::for_registry(&registry) {
                         Ok(client) => {
-                            match client.get_tags(&repo_clone).await {
-                                Ok(tags_response) => {
-                                    let tags = tags_response.tags.unwrap_or_default();
+                            // Walk every page so the deletion covers all tags
+                            match client.list_all_tags(&repo_clone, page_size).await {
+                                Ok(tags) => {
                                     delete_tags.set(tags);
                                     show_delete_dialog.set(true);
                                 }
@@ -148,8 +133,7 @@ pub fn RepositoryList() -> Element {
         show_delete_dialog.set(false);
         delete_repo_name.set(String::new());
         delete_tags.set(Vec::new());
-        delete_registry_url.set(String::new());
-        delete_registry_auth.set(AuthConfig::Anonymous);
+        delete_registry.set(None);
     };
     
     rsx! {
@@ -185,43 +169,48 @@ pub fn RepositoryList() -> Element {
             
             // Delete dialog
             if show_delete_dialog() && !delete_repo_name().is_empty() {
-                DeleteRepositoryDialog {
-                    repo_name: delete_repo_name(),
-                    tags: delete_tags(),
-                    registry_url: delete_registry_url(),
-                    registry_auth: delete_registry_auth(),
-                    on_confirm: move |result: DeletionResult| {
-                        let deleted_repo = delete_repo_name();
-                        close_dialog();
-                        
-                        // Show result status
-                        if result.failed == 0 {
-                            delete_status.set(Some(format!("Deleted {} tags successfully", result.deleted)));
-                        } else {
-                            delete_status.set(Some(format!("Deleted {}, {} failed", result.deleted, result.failed)));
-                        }
-                        
-                        // Refresh repository list
-                        if let Some(id) = app_state.selected_registry.read().clone() {
-                            if let Some(registry) = app_state.get_registry(&id) {
-                                spawn(async move {
-                                    if let Ok(client) = RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
-                                        if let Ok(catalog) = client.get_catalog(None).await {
-                                            repositories.set(catalog.repositories);
-                                        }
-                                    }
-                                });
+                if let Some(registry) = delete_registry() {
+                    DeleteRepositoryDialog {
+                        repo_name: delete_repo_name(),
+                        tags: delete_tags(),
+                        registry,
+                        on_confirm: move |result: DeletionResult| {
+                            let deleted_repo = delete_repo_name();
+                            close_dialog();
+                            
+                            // Show result status
+                            if result.failed() == 0 && result.cancelled() == 0 {
+                                delete_status.set(Some(format!("Deleted {} tags successfully", result.deleted())));
+                            } else if result.cancelled() > 0 {
+                                delete_status.set(Some(format!(
+                                    "Cancelled: deleted {}, {} failed, {} not attempted",
+                                    result.deleted(),
+                                    result.failed(),
+                                    result.cancelled()
+                                )));
+                            } else {
+                                delete_status.set(Some(format!("Deleted {}, {} failed", result.deleted(), result.failed())));
                             }
-                        }
-                        
-                        // Clear selection if deleted repo was selected
-                        if app_state.selected_repo.read().as_ref() == Some(&deleted_repo) {
-                            app_state.select_repo(None);
-                        }
-                    },
-                    on_cancel: move |_| {
-                        close_dialog();
-                    },
+                            
+                            // Refresh repository list
+                            if let Some(id) = app_state.selected_registry.read().clone() {
+                                if let Some(registry) = app_state.get_registry(&id) {
+                                    let page_size = app_state.cache_config.peek().page_size;
+                                    spawn(async move {
+                                        let _ = load_repositories(registry, page_size, repositories).await;
+                                    });
+                                }
+                            }
+                            
+                            // Clear selection if deleted repo was selected
+                            if app_state.selected_repo.read().as_ref() == Some(&deleted_repo) {
+                                app_state.select_repo(None);
+                            }
+                        },
+                        on_cancel: move |_| {
+                            close_dialog();
+                        },
+                    }
                 }
             }
             
@@ -242,12 +231,12 @@ pub fn RepositoryList() -> Element {
                     }
                 }
                 
-                if loading() {
+                if loading() && repositories.read().is_empty() {
                     p { class: "loading", "Loading..." }
                 } else if let Some(err) = error() {
                     div {
                         class: "error-box",
-                        p { class: "error", "{err}" }
+                        ErrorDisplay { error: err }
                         button {
                             class: "secondary small",
                             onclick: refresh,
@@ -264,12 +253,16 @@ pub fn RepositoryList() -> Element {
                         }
                     }
                 } else {
+                    if loading() {
+                        p { class: "loading-more", "Loaded {repositories.read().len()} repositories, loading more..." }
+                    }
                     div {
                         class: "list",
                         for repo in filtered() {
                             RepositoryItem {
                                 repo: repo.clone(),
                                 is_selected: selected_repo.as_ref() == Some(&repo),
+                                delete_support,
                                 on_select: move |name: String| app_state.select_repo(Some(name)),
                                 on_delete: move |name: String| initiate_delete(name),
                             }
@@ -281,11 +274,48 @@ pub fn RepositoryList() -> Element {
     }
 }
 
+/// Load all catalog pages, showing partial results as each page arrives
+async fn load_repositories(
+    registry: RegistryConfig,
+    page_size: usize,
+    mut repositories: Signal<Vec<String>>,
+) -> Result<(), ApiError> {
+    let client = RegistryClient::for_registry(&registry)?;
+    
+    let mut first_page = true;
+    client
+        .list_all_repositories_with(page_size, |page| {
+            // Replace stale results only once the first page is in
+            if first_page {
+                repositories.set(page.to_vec());
+                first_page = false;
+            } else {
+                repositories.write().extend_from_slice(page);
+            }
+        })
+        .await
+}
+
+/// Probe once per registry whether deletes are accepted, using any repository
+async fn probe_delete_support(registry: RegistryConfig, repo: String, mut app_state: AppState) {
+    if app_state.get_delete_support(&registry.id) != DeleteSupport::Unknown {
+        return;
+    }
+    let Ok(client) = RegistryClient::for_registry(&registry) else {
+        return;
+    };
+    // A failed probe stays Unknown and delete actions remain available
+    if let Ok(support) = client.probe_delete_support(&repo).await {
+        app_state.set_delete_support(&registry.id, support);
+    }
+}
+
 /// Single repository item
 #[component]
 fn RepositoryItem(
     repo: String,
     is_selected: bool,
+    delete_support: DeleteSupport,
     on_select: EventHandler<String>,
     on_delete: EventHandler<String>,
 ) -> Element {
@@ -301,7 +331,8 @@ fn RepositoryItem(
             
             button {
                 class: "btn-icon small danger",
-                title: "Delete repository",
+                title: delete_support.explanation().unwrap_or("Delete repository"),
+                disabled: !delete_support.allows_delete(),
                 onclick: move |e| {
                     e.stop_propagation();
                     on_delete.call(repo_delete.clone());
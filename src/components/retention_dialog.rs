@@ -0,0 +1,468 @@
+//! Tag retention policy dialog with dry-run preview
+
+use dioxus::prelude::*;
+use crate::api::{retry_transient, RegistryClient, TAG_INFO_CONCURRENCY};
+use crate::models::{DeletionEntry, RateLimitQuota, DeletionResult, DeletionStatus, RegistryConfig, RetentionPlan, RetentionPolicy, RetentionRule, SavedRetentionPolicy, TagDecision, TagInfo};
+use crate::state::AppState;
+use crate::utils::evaluate_retention;
+
+/// Retention policy dialog
+///
+/// Rules are edited, previewed as a dry run against the repository's tags and only
+/// then applied. `on_close` receives `true` when manifests were deleted.
+#[component]
+pub fn RetentionDialog(
+    registry: RegistryConfig,
+    repo: String,
+    tags: Vec<TagInfo>,
+    on_close: EventHandler<bool>,
+) -> Element {
+    let mut policy = use_signal(RetentionPolicy::default);
+    let mut plan = use_signal(|| None::<RetentionPlan>);
+    let mut plan_error = use_signal(|| None::<String>);
+    let mut resolving = use_signal(|| None::<(usize, usize)>);
+    let mut quota_warning = use_signal(|| None::<(usize, RateLimitQuota)>);
+    let mut deleting = use_signal(|| false);
+    let mut progress = use_signal(|| 0usize);
+    let mut result = use_signal(|| None::<DeletionResult>);
+    let mut saved = use_signal(|| false);
+    let mut app_state = use_context::<AppState>();
+    let delete_support = app_state.get_delete_support(&registry.id);
+    
+    // Any edit invalidates the preview
+    let mut invalidate_plan = move || {
+        plan.set(None);
+        plan_error.set(None);
+        saved.set(false);
+    };
+    
+    // Save the current rules as a policy scoped to exactly this repository
+    let registry_id = registry.id.clone();
+    let repo_save = repo.clone();
+    let save_policy = move |_| {
+        let mut saved_policy = SavedRetentionPolicy::new(
+            format!("{} retention", repo_save),
+            registry_id.clone(),
+            format!("re:^{}$", regex::escape(&repo_save)),
+        );
+        saved_policy.policy = policy.peek().clone();
+        app_state.add_retention_policy(saved_policy);
+        saved.set(true);
+    };
+    
+    let registry_preview = registry.clone();
+    let repo_preview = repo.clone();
+    let preview = move |_| {
+        let registry = registry_preview.clone();
+        let repo = repo_preview.clone();
+        let mut tags = tags.clone();
+        plan.set(None);
+        plan_error.set(None);
+        // A second Preview after the quota warning resolves anyway
+        let quota_confirmed = quota_warning.take().is_some();
+        
+        spawn(async move {
+            // Rules need digests and creation dates, resolve rows the list has not enriched yet
+            let pending: Vec<String> = tags
+                .iter()
+                .filter(|t| !t.is_enriched())
+                .map(|t| t.name.clone())
+                .collect();
+            if !pending.is_empty() {
+                let client = match RegistryClient::for_registry(&registry) {
+                    Ok(client) => client,
+                    Err(e) => {
+                        plan_error.set(Some(format!("Client error: {}", e)));
+                        return;
+                    }
+                };
+                
+                let total = pending.len();
+                if !quota_confirmed {
+                    if let Some(quota) = client.quota_shortfall(&repo, &pending[0], total).await {
+                        quota_warning.set(Some((total, quota)));
+                        return;
+                    }
+                }
+                
+                let mut done = 0;
+                resolving.set(Some((0, total)));
+                client
+                    .get_tag_infos_with(&repo, pending, TAG_INFO_CONCURRENCY, |_, result| {
+                        done += 1;
+                        if let Ok(info) = result {
+                            if let Some(row) = tags.iter_mut().find(|t| t.name == info.name) {
+                                *row = info;
+                            }
+                        }
+                        resolving.set(Some((done, total)));
+                    })
+                    .await;
+                resolving.set(None);
+            }
+            
+            match evaluate_retention(&policy.peek(), &tags, chrono::Utc::now()) {
+                Ok(p) => plan.set(Some(p)),
+                Err(e) => plan_error.set(Some(e)),
+            }
+        });
+    };
+    
+    let apply = move |_| {
+        let Some(current) = plan() else {
+            return;
+        };
+        let registry = registry.clone();
+        let repo = repo.clone();
+        deleting.set(true);
+        progress.set(0);
+        
+        let running = app_state.begin_deletion();
+        spawn(async move {
+            let _running = running;
+            let mut entries = Vec::new();
+            
+            match RegistryClient::for_registry(&registry) {
+                Ok(client) => {
+                    for (i, digest) in current.digests_to_delete().iter().enumerate() {
+                        let (outcome, attempts) =
+                            retry_transient(client.max_attempts(), || client.delete_manifest(&repo, digest)).await;
+                        let (status, error) = match outcome {
+                            Ok(_) => (DeletionStatus::Deleted, None),
+                            Err(e) => (DeletionStatus::Failed, Some(e.to_string())),
+                        };
+                        for decision in current.to_delete().into_iter().filter(|d| &d.tag.digest == digest) {
+                            entries.push(DeletionEntry {
+                                tag: decision.tag.name.clone(),
+                                digest: digest.clone(),
+                                status,
+                                error: error.clone(),
+                                attempts,
+                            });
+                        }
+                        progress.set(i + 1);
+                    }
+                }
+                Err(e) => {
+                    for decision in current.to_delete() {
+                        entries.push(DeletionEntry {
+                            tag: decision.tag.name.clone(),
+                            digest: decision.tag.digest.clone(),
+                            status: DeletionStatus::Failed,
+                            error: Some(format!("Client error: {}", e)),
+                            attempts: 0,
+                        });
+                    }
+                }
+            }
+            
+            result.set(Some(DeletionResult { entries }));
+            deleting.set(false);
+        });
+    };
+    
+    let rules = policy.read().rules.clone();
+    let digest_total = plan.read().as_ref().map(|p| p.digests_to_delete().len()).unwrap_or(0);
+    
+    rsx! {
+        div {
+            class: "modal-overlay",
+            onclick: move |_| {
+                if !deleting() {
+                    on_close.call(result().is_some());
+                }
+            },
+            
+            div {
+                class: "modal retention-dialog",
+                onclick: move |e| e.stop_propagation(),
+                
+                h3 { "Retention Policy" }
+                
+                if let Some(res) = result() {
+                    div {
+                        class: "delete-summary",
+                        p {
+                            "Deleted "
+                            strong { "{res.deleted()}" }
+                            " tag(s)."
+                        }
+                        if res.failed() > 0 {
+                            div {
+                                class: "error-summary",
+                                p { class: "error", "Failed to delete {res.failed()} tag(s):" }
+                                div {
+                                    class: "error-list",
+                                    for err in res.errors() {
+                                        p { class: "error-item", "{err}" }
+                                    }
+                                }
+                            }
+                        }
+                        p { class: "hint", "Run garbage collection on the registry to reclaim blob storage." }
+                        div {
+                            class: "form-actions",
+                            button {
+                                class: "primary",
+                                onclick: move |_| on_close.call(true),
+                                "Close"
+                            }
+                        }
+                    }
+                } else if deleting() {
+                    div {
+                        class: "delete-progress",
+                        p { "Deleting manifests..." }
+                        div {
+                            class: "progress-bar",
+                            div {
+                                class: "progress-fill",
+                                style: "width: {(progress() * 100) / digest_total.max(1)}%",
+                            }
+                        }
+                        p { "{progress()} / {digest_total}" }
+                    }
+                } else {
+                    div {
+                        class: "retention-rules",
+                        for (index, rule) in rules.into_iter().enumerate() {
+                            RetentionRuleRow {
+                                key: "{index}",
+                                rule,
+                                on_change: move |rule: RetentionRule| {
+                                    policy.write().rules[index] = rule;
+                                    invalidate_plan();
+                                },
+                                on_remove: move |_| {
+                                    policy.write().rules.remove(index);
+                                    invalidate_plan();
+                                },
+                            }
+                        }
+                        button {
+                            class: "secondary small",
+                            onclick: move |_| {
+                                policy.write().rules.push(RetentionRule::DeleteOlderThan {
+                                    pattern: "*".to_string(),
+                                    days: 90,
+                                });
+                                invalidate_plan();
+                            },
+                            "+ Add Rule"
+                        }
+                        p { class: "hint", "Patterns use the tag search syntax: globs (release-*), /regex/ or substrings. Keep rules win over delete rules." }
+                    }
+                    
+                    if let Some((done, total)) = resolving() {
+                        p { class: "loading-more", "Resolving tag details {done} / {total}..." }
+                    }
+                    if let Some(err) = plan_error() {
+                        p { class: "error", "{err}" }
+                    }
+                    if let Some((needed, quota)) = quota_warning() {
+                        p {
+                            class: "quota-warning",
+                            "⚠️ {needed} tags still need details, which takes a manifest pull per tag, two for multi-arch images, "
+                            "but only {quota.remaining} of {quota.limit} are left on this registry. "
+                            "Click Preview again to resolve them anyway."
+                        }
+                    }
+                    
+                    if let Some(current) = plan() {
+                        RetentionPreview { plan: current }
+                    }
+                    if let Some(reason) = delete_support.explanation() {
+                        p { class: "hint", "{reason} The preview still shows what the policy would delete." }
+                    }
+                    if saved() {
+                        p { class: "hint", "Saved as a retention policy. Enable its schedule under Settings → Retention Policies." }
+                    }
+                    
+                    div {
+                        class: "form-actions",
+                        button {
+                            class: "secondary",
+                            onclick: move |_| on_close.call(false),
+                            "Cancel"
+                        }
+                        button {
+                            class: "secondary",
+                            disabled: saved(),
+                            onclick: save_policy,
+                            "Save as Policy"
+                        }
+                        button {
+                            class: "secondary",
+                            disabled: resolving().is_some(),
+                            onclick: preview,
+                            "Preview"
+                        }
+                        if plan().is_some_and(|p| !p.to_delete().is_empty()) && delete_support.allows_delete() {
+                            button {
+                                class: "danger",
+                                onclick: apply,
+                                "Delete {plan().map(|p| p.to_delete().len()).unwrap_or(0)} Tag(s)"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Editable row for a single rule
+#[component]
+pub(crate) fn RetentionRuleRow(
+    rule: RetentionRule,
+    on_change: EventHandler<RetentionRule>,
+    on_remove: EventHandler<()>,
+) -> Element {
+    let kind = match &rule {
+        RetentionRule::KeepNewest { .. } => "keep_newest",
+        RetentionRule::DeleteOlderThan { .. } => "delete_older",
+        RetentionRule::KeepReleases => "keep_releases",
+    };
+    let pattern = match &rule {
+        RetentionRule::KeepNewest { pattern, .. } | RetentionRule::DeleteOlderThan { pattern, .. } => pattern.clone(),
+        RetentionRule::KeepReleases => String::new(),
+    };
+    let number = match &rule {
+        RetentionRule::KeepNewest { count, .. } => *count as u64,
+        RetentionRule::DeleteOlderThan { days, .. } => *days as u64,
+        RetentionRule::KeepReleases => 0,
+    };
+    
+    let rule_pattern = rule.clone();
+    let rule_number = rule.clone();
+    
+    rsx! {
+        div {
+            class: "retention-rule",
+            select {
+                value: kind,
+                onchange: move |e| {
+                    let pattern = "*".to_string();
+                    on_change.call(match e.value().as_str() {
+                        "keep_newest" => RetentionRule::KeepNewest { pattern, count: 10 },
+                        "delete_older" => RetentionRule::DeleteOlderThan { pattern, days: 90 },
+                        _ => RetentionRule::KeepReleases,
+                    });
+                },
+                option { value: "keep_newest", "Keep newest" }
+                option { value: "delete_older", "Delete older than (days)" }
+                option { value: "keep_releases", "Keep semver releases (incl. variants like -alpine)" }
+            }
+            
+            if rule != RetentionRule::KeepReleases {
+                input {
+                    r#type: "number",
+                    class: "rule-number",
+                    min: "0",
+                    value: "{number}",
+                    oninput: move |e| {
+                        let value = e.value().parse::<u64>().unwrap_or(0);
+                        on_change.call(match rule_number.clone() {
+                            RetentionRule::KeepNewest { pattern, .. } => RetentionRule::KeepNewest { pattern, count: value as usize },
+                            RetentionRule::DeleteOlderThan { pattern, .. } => RetentionRule::DeleteOlderThan { pattern, days: value as u32 },
+                            other => other,
+                        });
+                    },
+                }
+                input {
+                    r#type: "text",
+                    class: "rule-pattern",
+                    placeholder: "Tag pattern",
+                    value: "{pattern}",
+                    oninput: move |e| {
+                        let pattern = e.value();
+                        on_change.call(match rule_pattern.clone() {
+                            RetentionRule::KeepNewest { count, .. } => RetentionRule::KeepNewest { pattern, count },
+                            RetentionRule::DeleteOlderThan { days, .. } => RetentionRule::DeleteOlderThan { pattern, days },
+                            other => other,
+                        });
+                    },
+                }
+            }
+            
+            button {
+                class: "btn-icon small",
+                title: "Remove rule",
+                onclick: move |_| on_remove.call(()),
+                "×"
+            }
+        }
+    }
+}
+
+/// Dry-run result listing deleted and kept tags with reasons
+#[component]
+fn RetentionPreview(plan: RetentionPlan) -> Element {
+    let to_delete: Vec<TagDecision> = plan.to_delete().into_iter().cloned().collect();
+    let to_keep: Vec<TagDecision> = plan.to_keep().into_iter().cloned().collect();
+    let digest_count = plan.digests_to_delete().len();
+    let unresolved: Vec<String> = plan.unresolved().into_iter().map(|d| d.tag.name.clone()).collect();
+    let unresolved_names = unresolved.join(", ");
+    
+    rsx! {
+        div {
+            class: "retention-preview",
+            if !unresolved.is_empty() {
+                p {
+                    class: "warning",
+                    "⚠️ Digest unknown for {unresolved_names}. It may share a manifest with other tags, "
+                    "so nothing in this repository will be deleted."
+                }
+            }
+            p {
+                strong { "{to_delete.len()}" }
+                " tag(s) would be deleted ({digest_count} manifest(s)), "
+                strong { "{to_keep.len()}" }
+                " kept."
+            }
+            
+            if !to_delete.is_empty() {
+                h4 { "Delete" }
+                div {
+                    class: "retention-list delete",
+                    for decision in to_delete {
+                        RetentionDecisionRow { decision }
+                    }
+                }
+            }
+            
+            if !to_keep.is_empty() {
+                h4 { "Keep" }
+                div {
+                    class: "retention-list keep",
+                    for decision in to_keep {
+                        RetentionDecisionRow { decision }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Single tag in the dry-run preview
+#[component]
+fn RetentionDecisionRow(decision: TagDecision) -> Element {
+    let created = decision
+        .tag
+        .created
+        .as_deref()
+        .and_then(|c| chrono::DateTime::parse_from_rfc3339(c).ok())
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    
+    rsx! {
+        div {
+            class: "retention-decision",
+            span { class: "tag-name", "{decision.tag.name}" }
+            if !created.is_empty() {
+                span { class: "tag-created", "{created}" }
+            }
+            span { class: "retention-reason", "{decision.reason}" }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Saved retention policies and their run log
+
+use dioxus::prelude::*;
+use crate::state::{execute_retention_policy, AppState};
+use crate::models::{RegistryConfig, RetentionRule, RetentionRun, SavedRetentionPolicy};
+use crate::components::retention_dialog::RetentionRuleRow;
+
+/// Settings sections for managing saved retention policies
+#[component]
+pub fn RetentionPolicies() -> Element {
+    let mut app_state = use_context::<AppState>();
+    let mut editing = use_signal(|| None::<SavedRetentionPolicy>);
+    
+    let registries = app_state.registries.read().clone();
+    let policies = app_state.retention_policies.read().clone();
+    let running = app_state.running_policies.read().clone();
+    let log = app_state.retention_log.read().clone();
+    
+    let first_registry_id = registries.first().map(|r| r.id.clone());
+    
+    rsx! {
+        section {
+            class: "settings-section",
+            h3 { "Retention Policies" }
+            p { class: "hint", "Enabled policies run automatically while the app is open. Every run is recorded in the run log below." }
+            
+            if policies.is_empty() {
+                p { class: "empty-message", "No retention policies saved" }
+            }
+            
+            div {
+                class: "policy-list",
+                for policy in policies {
+                    RetentionPolicyItem {
+                        key: "{policy.id}",
+                        registry_name: registries
+                            .iter()
+                            .find(|r| r.id == policy.registry_id)
+                            .map(|r| r.name.clone())
+                            .unwrap_or_else(|| "Unknown registry".to_string()),
+                        is_running: running.contains(&policy.id),
+                        policy,
+                        on_edit: move |p: SavedRetentionPolicy| editing.set(Some(p)),
+                        on_toggle: move |p: SavedRetentionPolicy| app_state.update_retention_policy(p),
+                        on_run: move |p: SavedRetentionPolicy| {
+                            spawn(async move {
+                                execute_retention_policy(app_state, p, false).await;
+                            });
+                        },
+                        on_delete: move |id: String| app_state.delete_retention_policy(&id),
+                    }
+                }
+            }
+            
+            button {
+                class: "secondary",
+                disabled: first_registry_id.is_none(),
+                onclick: move |_| {
+                    if let Some(registry_id) = first_registry_id.clone() {
+                        editing.set(Some(SavedRetentionPolicy::new(
+                            "New policy".to_string(),
+                            registry_id,
+                            "*".to_string(),
+                        )));
+                    }
+                },
+                "+ Add Policy"
+            }
+            
+            if let Some(policy) = editing() {
+                RetentionPolicyEditor {
+                    policy,
+                    registries: registries.clone(),
+                    on_save: move |p: SavedRetentionPolicy| {
+                        if app_state.retention_policies.peek().iter().any(|existing| existing.id == p.id) {
+                            app_state.update_retention_policy(p);
+                        } else {
+                            app_state.add_retention_policy(p);
+                        }
+                        editing.set(None);
+                    },
+                    on_cancel: move |_| editing.set(None),
+                }
+            }
+        }
+        
+        section {
+            class: "settings-section",
+            h3 { "Retention Run Log" }
+            
+            if log.is_empty() {
+                p { class: "empty-message", "No retention runs yet" }
+            } else {
+                div {
+                    class: "run-log",
+                    for (index, run) in log.into_iter().enumerate() {
+                        RetentionRunItem { key: "{index}-{run.started_at}", run }
+                    }
+                }
+                button {
+                    class: "danger",
+                    onclick: move |_| app_state.clear_retention_log(),
+                    "Clear Run Log"
+                }
+            }
+        }
+    }
+}
+
+/// Single saved policy with its schedule and actions
+#[component]
+fn RetentionPolicyItem(
+    policy: SavedRetentionPolicy,
+    registry_name: String,
+    is_running: bool,
+    on_edit: EventHandler<SavedRetentionPolicy>,
+    on_toggle: EventHandler<SavedRetentionPolicy>,
+    on_run: EventHandler<SavedRetentionPolicy>,
+    on_delete: EventHandler<String>,
+) -> Element {
+    let schedule = if policy.interval_hours == 0 {
+        "Manual only".to_string()
+    } else {
+        format!("Every {} hour(s)", policy.interval_hours)
+    };
+    let last_run = policy.last_run.map(format_timestamp).unwrap_or_else(|| "Never".to_string());
+    
+    let policy_toggle = policy.clone();
+    let policy_edit = policy.clone();
+    let policy_run = policy.clone();
+    let id_delete = policy.id.clone();
+    
+    rsx! {
+        div {
+            class: "policy-item",
+            
+            label {
+                class: "policy-enabled",
+                title: "Run on schedule",
+                input {
+                    r#type: "checkbox",
+                    checked: policy.enabled,
+                    onchange: move |e| {
+                        on_toggle.call(SavedRetentionPolicy {
+                            enabled: e.checked(),
+                            ..policy_toggle.clone()
+                        });
+                    },
+                }
+            }
+            
+            div {
+                class: "policy-info",
+                span { class: "policy-name", "{policy.name}" }
+                span { class: "policy-scope", "{registry_name} · {policy.repository_pattern}" }
+                span { class: "policy-schedule", "{schedule} · last run: {last_run}" }
+            }
+            
+            div {
+                class: "policy-actions",
+                button {
+                    class: "secondary small",
+                    disabled: is_running,
+                    onclick: move |_| on_run.call(policy_run.clone()),
+                    if is_running { "Running..." } else { "Run Now" }
+                }
+                button {
+                    class: "btn-icon small",
+                    title: "Edit policy",
+                    onclick: move |_| on_edit.call(policy_edit.clone()),
+                    "✎"
+                }
+                button {
+                    class: "btn-icon small danger",
+                    title: "Delete policy",
+                    onclick: move |_| on_delete.call(id_delete.clone()),
+                    "×"
+                }
+            }
+        }
+    }
+}
+
+/// Modal editor for a saved policy
+#[component]
+fn RetentionPolicyEditor(
+    policy: SavedRetentionPolicy,
+    registries: Vec<RegistryConfig>,
+    on_save: EventHandler<SavedRetentionPolicy>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let mut draft = use_signal(|| policy.clone());
+    let current = draft();
+    let rules = current.policy.rules.clone();
+    
+    rsx! {
+        div {
+            class: "modal-overlay",
+            
+            div {
+                class: "modal retention-dialog",
+                onclick: move |e| e.stop_propagation(),
+                
+                h3 { "Retention Policy" }
+                
+                form {
+                    onsubmit: move |e| {
+                        e.prevent_default();
+                        on_save.call(draft());
+                    },
+                    
+                    div {
+                        class: "form-group",
+                        label { "Name" }
+                        input {
+                            r#type: "text",
+                            value: "{current.name}",
+                            oninput: move |e| draft.write().name = e.value(),
+                            required: true,
+                        }
+                    }
+                    
+                    div {
+                        class: "form-group",
+                        label { "Registry" }
+                        select {
+                            value: "{current.registry_id}",
+                            onchange: move |e| draft.write().registry_id = e.value(),
+                            for registry in registries.iter() {
+                                option { value: "{registry.id}", "{registry.name}" }
+                            }
+                        }
+                    }
+                    
+                    div {
+                        class: "form-group",
+                        label { "Repositories" }
+                        input {
+                            r#type: "text",
+                            value: "{current.repository_pattern}",
+                            placeholder: "team/*",
+                            oninput: move |e| draft.write().repository_pattern = e.value(),
+                        }
+                    }
+                    
+                    div {
+                        class: "form-group",
+                        label { "Run every (hours, 0 = manual only)" }
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            value: "{current.interval_hours}",
+                            oninput: move |e| {
+                                if let Ok(hours) = e.value().parse() {
+                                    draft.write().interval_hours = hours;
+                                }
+                            },
+                        }
+                    }
+                    
+                    div {
+                        class: "form-group checkbox",
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: current.enabled,
+                                onchange: move |e| draft.write().enabled = e.checked(),
+                            }
+                            "Run on schedule"
+                        }
+                    }
+                    
+                    div {
+                        class: "retention-rules",
+                        for (index, rule) in rules.into_iter().enumerate() {
+                            RetentionRuleRow {
+                                key: "{index}",
+                                rule,
+                                on_change: move |rule: RetentionRule| draft.write().policy.rules[index] = rule,
+                                on_remove: move |_| {
+                                    draft.write().policy.rules.remove(index);
+                                },
+                            }
+                        }
+                        button {
+                            r#type: "button",
+                            class: "secondary small",
+                            onclick: move |_| {
+                                draft.write().policy.rules.push(RetentionRule::DeleteOlderThan {
+                                    pattern: "*".to_string(),
+                                    days: 90,
+                                });
+                            },
+                            "+ Add Rule"
+                        }
+                        p { class: "hint", "Repository and tag patterns use the tag search syntax: globs (team/*), /regex/ or substrings." }
+                    }
+                    
+                    div {
+                        class: "form-actions",
+                        button {
+                            r#type: "button",
+                            class: "secondary",
+                            onclick: move |_| on_cancel.call(()),
+                            "Cancel"
+                        }
+                        button {
+                            r#type: "submit",
+                            class: "primary",
+                            "Save"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Single entry of the run log, expandable to show every tag
+#[component]
+fn RetentionRunItem(run: RetentionRun) -> Element {
+    let mut expanded = use_signal(|| false);
+    let started = format_timestamp(run.started_at);
+    let trigger = if run.scheduled { "scheduled" } else { "manual" };
+    let has_problems = !run.failed.is_empty() || !run.errors.is_empty();
+    
+    rsx! {
+        div {
+            class: if has_problems { "run-item has-errors" } else { "run-item" },
+            
+            div {
+                class: "run-summary",
+                onclick: move |_| expanded.set(!expanded()),
+                span { class: "run-toggle", if expanded() { "▼" } else { "▶" } }
+                span { class: "policy-name", "{run.policy_name}" }
+                span { class: "run-time", "{started} ({trigger})" }
+                span { class: "run-counts", "{run.deleted.len()} deleted, {run.failed.len()} failed" }
+            }
+            
+            if expanded() {
+                div {
+                    class: "run-details",
+                    for err in run.errors.iter() {
+                        p { class: "error-item", "{err}" }
+                    }
+                    for entry in run.failed.iter() {
+                        p {
+                            class: "error-item",
+                            "{entry.repository}:{entry.tag} — {entry.error.clone().unwrap_or_default()}"
+                        }
+                    }
+                    for entry in run.deleted.iter() {
+                        p {
+                            class: "run-entry",
+                            title: "{entry.digest}",
+                            "{entry.repository}:{entry.tag}"
+                        }
+                    }
+                    if run.deleted.is_empty() && !has_problems {
+                        p { class: "hint", "Nothing to delete." }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Format a Unix timestamp for display in local time
+fn format_timestamp(ts: u64) -> String {
+    chrono::DateTime::from_timestamp(ts as i64, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
@@ -3,6 +3,8 @@
 use dioxus::prelude::*;
 use crate::state::AppState;
 use crate::models::Theme;
+use crate::storage::{get_storage, CredentialBackend};
+use super::RetentionPolicies;
 
 /// Settings panel component
 #[component]
@@ -85,6 +87,24 @@ pub fn Settings() -> Element {
                     }
                 }
                 
+                div {
+                    class: "form-group",
+                    label { "Page size for repository and tag listing" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "10000",
+                        value: "{cache_config().page_size}",
+                        onchange: move |e| {
+                            if let Ok(val) = e.value().parse::<usize>() {
+                                let mut config = cache_config();
+                                config.page_size = val.max(1);
+                                app_state.set_cache_config(config);
+                            }
+                        },
+                    }
+                }
+                
                 button {
                     class: "danger",
                     onclick: move |_| {
@@ -95,6 +115,12 @@ pub fn Settings() -> Element {
                 }
             }
             
+            // Saved retention policies and run log
+            RetentionPolicies {}
+            
+            // Master password
+            MasterPassword {}
+            
             // Import/Export
             section {
                 class: "settings-section",
@@ -159,6 +185,267 @@ pub fn Settings() -> Element {
                         "Import"
                     }
                 }
+                
+                // Registries and credentials from the docker CLI
+                DockerConfigImporter {}
+            }
+        }
+    }
+}
+
+/// Import of registries and credentials from the docker CLI config
+#[cfg(not(target_arch = "wasm32"))]
+#[component]
+fn DockerConfigImporter() -> Element {
+    let mut app_state = use_context::<AppState>();
+    let mut path = use_signal(|| {
+        crate::utils::default_docker_config_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default()
+    });
+    let mut use_helpers = use_signal(|| true);
+    let mut importing = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut summary = use_signal(|| None::<String>);
+    let mut warnings = use_signal(Vec::<String>::new);
+    
+    let handle_import = move |_| {
+        importing.set(true);
+        error.set(None);
+        summary.set(None);
+        warnings.set(Vec::new());
+        spawn(async move {
+            let config_path = std::path::PathBuf::from(path());
+            match crate::utils::import_docker_config(&config_path, use_helpers()).await {
+                Ok(import) => {
+                    // Servers that are already configured are left alone
+                    let existing: Vec<String> = app_state.registries.read()
+                        .iter()
+                        .map(|r| r.url.trim_end_matches('/').to_string())
+                        .collect();
+                    let (new, skipped): (Vec<_>, Vec<_>) = import.registries
+                        .into_iter()
+                        .partition(|r| !existing.contains(&r.url));
+                    let added = new.len();
+                    for registry in new {
+                        app_state.add_registry(registry);
+                    }
+                    summary.set(Some(format!("Imported {} registries, skipped {} already configured.", added, skipped.len())));
+                    warnings.set(import.warnings);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            importing.set(false);
+        });
+    };
+    
+    rsx! {
+        div {
+            class: "form-group",
+            label { "Import from Docker Config" }
+            input {
+                r#type: "text",
+                value: "{path}",
+                placeholder: "~/.docker/config.json",
+                oninput: move |e| path.set(e.value()),
+            }
+            label {
+                class: "command-env",
+                input {
+                    r#type: "checkbox",
+                    checked: use_helpers(),
+                    onchange: move |e| use_helpers.set(e.checked()),
+                }
+                "Query credential helpers (docker-credential-*)"
+            }
+            
+            if let Some(err) = error() {
+                p { class: "error", "{err}" }
+            }
+            if let Some(message) = summary() {
+                p { class: "hint", "{message}" }
+            }
+            for warning in warnings() {
+                p { class: "error", "{warning}" }
+            }
+            
+            button {
+                class: "primary",
+                disabled: importing() || path().is_empty(),
+                onclick: handle_import,
+                if importing() { "Importing..." } else { "Import Docker Config" }
+            }
+        }
+    }
+}
+
+/// Docker config import needs the file system and credential helpers
+#[cfg(target_arch = "wasm32")]
+#[component]
+fn DockerConfigImporter() -> Element {
+    rsx! {}
+}
+
+/// Settings section for the credential backend, the master password and the idle auto-lock
+#[component]
+fn MasterPassword() -> Element {
+    let mut app_state = use_context::<AppState>();
+    let auto_lock_minutes = app_state.auto_lock_minutes;
+    let mut backend = app_state.credential_backend;
+    let mut current = use_signal(String::new);
+    let mut new_password = use_signal(String::new);
+    let mut confirm = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut status = use_signal(|| None::<String>);
+    
+    let mut finish = move |message: &str| {
+        current.set(String::new());
+        new_password.set(String::new());
+        confirm.set(String::new());
+        error.set(None);
+        status.set(Some(message.to_string()));
+    };
+    
+    let check_new_password = move || {
+        if new_password().is_empty() {
+            Err("New password cannot be empty.".to_string())
+        } else if new_password() != confirm() {
+            Err("New passwords do not match.".to_string())
+        } else {
+            Ok(())
+        }
+    };
+    
+    let mut handle_change = move || {
+        status.set(None);
+        if let Err(e) = check_new_password() {
+            error.set(Some(e));
+            return;
+        }
+        
+        match get_storage().change_password(&current(), &new_password()) {
+            Ok(()) => finish("Master password changed. Stored credentials were re-encrypted."),
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    };
+    
+    let mut handle_use_password = move || {
+        status.set(None);
+        if let Err(e) = check_new_password() {
+            error.set(Some(e));
+            return;
+        }
+        
+        let registries = app_state.registries.read().clone();
+        match get_storage().use_master_password(&registries, &new_password()) {
+            Ok(()) => {
+                backend.set(CredentialBackend::Encrypted);
+                finish("Credentials moved out of the system keychain and encrypted with the master password.");
+            }
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    };
+    
+    let handle_use_keychain = move |_| {
+        status.set(None);
+        let registries = app_state.registries.read().clone();
+        match get_storage().use_keychain(&registries) {
+            Ok(()) => {
+                backend.set(CredentialBackend::Keychain);
+                finish("Credentials moved to the system keychain. No master password is needed any more.");
+            }
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    };
+    
+    let uses_keychain = backend() == CredentialBackend::Keychain;
+    
+    rsx! {
+        section {
+            class: "settings-section",
+            h3 { "Security" }
+            
+            if uses_keychain {
+                p { class: "hint", "Passwords and tokens are stored in the system keychain." }
+                p {
+                    class: "hint",
+                    "Locking is not available: the keychain hands out credentials without a password, "
+                    "so your OS account protects them. Use a master password to lock the app."
+                }
+            } else {
+                div {
+                    class: "form-group",
+                    label { "Lock after idle minutes (0 = never)" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        value: "{auto_lock_minutes}",
+                        onchange: move |e| {
+                            if let Ok(minutes) = e.value().parse() {
+                                app_state.set_auto_lock_minutes(minutes);
+                            }
+                        },
+                    }
+                    p { class: "hint", "Scheduled retention policies do not run while the app is locked. Running deletions finish first." }
+                }
+                
+                div {
+                    class: "form-group",
+                    label { "Current password" }
+                    input {
+                        r#type: "password",
+                        value: "{current}",
+                        oninput: move |e| current.set(e.value()),
+                    }
+                }
+            }
+            
+            div {
+                class: "form-group",
+                label { "New password" }
+                input {
+                    r#type: "password",
+                    value: "{new_password}",
+                    oninput: move |e| new_password.set(e.value()),
+                }
+            }
+            
+            div {
+                class: "form-group",
+                label { "Confirm new password" }
+                input {
+                    r#type: "password",
+                    value: "{confirm}",
+                    oninput: move |e| confirm.set(e.value()),
+                }
+            }
+            
+            if let Some(err) = error() {
+                p { class: "error", "{err}" }
+            }
+            if let Some(message) = status() {
+                p { class: "hint", "{message}" }
+            }
+            
+            if uses_keychain {
+                button {
+                    class: "primary",
+                    disabled: new_password().is_empty(),
+                    onclick: move |_| handle_use_password(),
+                    "Use Master Password"
+                }
+            } else {
+                button {
+                    class: "primary",
+                    disabled: current().is_empty() || new_password().is_empty(),
+                    onclick: move |_| handle_change(),
+                    "Change Password"
+                }
+                button {
+                    class: "secondary",
+                    onclick: handle_use_keychain,
+                    "Move to System Keychain"
+                }
             }
         }
     }
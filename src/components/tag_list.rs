@@ -2,9 +2,10 @@
 
 use dioxus::prelude::*;
 use crate::state::AppState;
-use crate::api::RegistryClient;
-use crate::models::TagInfo;
-use crate::utils::format_size;
+use crate::api::{ApiError, RegistryClient, TAG_INFO_CONCURRENCY};
+use crate::models::{AppError, DeleteSupport, RateLimitQuota, RegistryConfig, TagInfo};
+use crate::components::{CommandPanel, ErrorDisplay, RetentionDialog};
+use crate::utils::{format_size, group_by_digest, sorted_tags, CommandTarget, DigestGroup, NameFilter, TagSortOrder};
 
 /// Tag list component
 #[component]
@@ -15,16 +16,23 @@ pub fn TagList() -> Element {
     let selected_tag = app_state.selected_tag.read().clone();
     
     let mut search = use_signal(String::new);
+    let mut sort_order = use_signal(TagSortOrder::default);
     let mut tags = use_signal(Vec::<TagInfo>::new);
     let mut selected_tags = use_signal(Vec::<String>::new);
     let mut loading = use_signal(|| false);
-    let mut error = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<AppError>);
     let mut delete_status = use_signal(|| None::<String>);
+    let mut enrich_progress = use_signal(|| None::<(usize, usize)>);
+    let mut quota_warning = use_signal(|| None::<(usize, RateLimitQuota)>);
     let mut show_delete_confirm = use_signal(|| false);
+    let mut show_retention = use_signal(|| false);
     
     // Get the selected registry config
     let selected_registry = selected_registry_id.as_ref()
         .and_then(|id| app_state.get_registry(id));
+    let delete_support = selected_registry_id.as_ref()
+        .map(|id| app_state.get_delete_support(id))
+        .unwrap_or_default();
     
     // Fetch tags when repo changes
     let _fetch = use_resource(move || {
@@ -35,37 +43,21 @@ pub fn TagList() -> Element {
                 if let Some(registry) = app_state.get_registry(&id) {
                     loading.set(true);
                     error.set(None);
+                    tags.set(Vec::new());
+                    enrich_progress.set(None);
+                    quota_warning.set(None);
                     
-                    match RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
-                        Ok(client) => {
-                            match client.get_tags(&repo_name).await {
-                                Ok(tags_response) => {
-                                    // Convert to TagInfo (without size/digest for now)
-                                    let tag_infos: Vec<TagInfo> = tags_response.tags
-                                        .unwrap_or_default()
-                                        .into_iter()
-                                        .map(|name| TagInfo {
-                                            name,
-                                            digest: String::new(),
-                                            size: 0,
-                                        })
-                                        .collect();
-                                    tags.set(tag_infos);
-                                    error.set(None);
-                                }
-                                Err(e) => {
-                                    error.set(Some(format!("Failed to fetch tags: {}", e)));
-                                    tags.set(Vec::new());
-                                }
-                            }
-                        }
+                    let page_size = app_state.cache_config.peek().page_size;
+                    let result = load_tags(registry.clone(), repo_name.clone(), page_size, tags).await;
+                    loading.set(false);
+                    
+                    match result {
+                        Ok(()) => enrich_tags(registry, repo_name, tags, enrich_progress, quota_warning, false).await,
                         Err(e) => {
-                            error.set(Some(format!("Failed to create client: {}", e)));
+                            error.set(Some(e.to_app_error()));
                             tags.set(Vec::new());
                         }
                     }
-                    
-                    loading.set(false);
                 }
             } else {
                 tags.set(Vec::new());
@@ -74,24 +66,21 @@ pub fn TagList() -> Element {
         }
     });
     
-    // Filter tags
+    // Parse the search box (substring, glob or regex)
+    let name_filter = use_memo(move || NameFilter::parse(&search()));
+    
+    // Filter and sort tags
     let filtered = use_memo(move || {
         let tag_list = tags.read();
-        let search_term = search();
-        if search_term.is_empty() {
-            tag_list.clone()
-        } else {
-            let search_lower = search_term.to_lowercase();
-            tag_list
-                .iter()
-                .filter(|t| t.name.to_lowercase().contains(&search_lower))
-                .cloned()
-                .collect()
-        }
+        let matching: Vec<TagInfo> = match &*name_filter.read() {
+            Ok(filter) => tag_list.iter().filter(|t| filter.matches(&t.name)).cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+        sorted_tags(&matching, sort_order())
     });
     
-    // Manual refresh function
-    let refresh = move |_| {
+    // Reload tags of the selected repository
+    let mut reload = move || {
         if let (Some(id), Some(repo_name)) = (
             app_state.selected_registry.read().clone(),
             app_state.selected_repo.read().clone()
@@ -100,37 +89,20 @@ pub fn TagList() -> Element {
                 loading.set(true);
                 error.set(None);
                 
+                let page_size = app_state.cache_config.peek().page_size;
                 spawn(async move {
-                    match RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
-                        Ok(client) => {
-                            match client.get_tags(&repo_name).await {
-                                Ok(tags_response) => {
-                                    let tag_infos: Vec<TagInfo> = tags_response.tags
-                                        .unwrap_or_default()
-                                        .into_iter()
-                                        .map(|name| TagInfo {
-                                            name,
-                                            digest: String::new(),
-                                            size: 0,
-                                        })
-                                        .collect();
-                                    tags.set(tag_infos);
-                                    error.set(None);
-                                }
-                                Err(e) => {
-                                    error.set(Some(format!("Failed to fetch: {}", e)));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            error.set(Some(format!("Client error: {}", e)));
-                        }
-                    }
+                    let result = load_tags(registry.clone(), repo_name.clone(), page_size, tags).await;
                     loading.set(false);
+                    
+                    match result {
+                        Ok(()) => enrich_tags(registry, repo_name, tags, enrich_progress, quota_warning, false).await,
+                        Err(e) => error.set(Some(e.to_app_error())),
+                    }
                 });
             }
         }
     };
+    let refresh = move |_| reload();
     
     rsx! {
         div {
@@ -143,6 +115,12 @@ pub fn TagList() -> Element {
                 if selected_repo.is_some() && selected_registry.is_some() {
                     div {
                         class: "header-actions",
+                        button {
+                            class: "btn-icon small",
+                            title: "Retention policy",
+                            onclick: move |_| show_retention.set(true),
+                            "🧹"
+                        }
                         button {
                             class: "btn-icon small",
                             title: "Refresh",
@@ -159,15 +137,42 @@ pub fn TagList() -> Element {
                     "Select a repository to view tags"
                 }
             } else {
-                // Search input
+                // Search input and sort order
                 div {
-                    class: "search-box",
+                    class: "search-box tag-search",
                     input {
                         r#type: "text",
-                        placeholder: "Search tags...",
+                        placeholder: "Search tags (release-*, /^v\\d+/)...",
+                        title: "Substring match; * and ? for globs; /regex/ or re:regex for regular expressions",
                         value: "{search}",
                         oninput: move |e| search.set(e.value()),
                     }
+                    select {
+                        class: "sort-select",
+                        title: "Sort order",
+                        value: match sort_order() {
+                            TagSortOrder::Name => "name",
+                            TagSortOrder::Version => "version",
+                            TagSortOrder::Created => "created",
+                            TagSortOrder::Size => "size",
+                        },
+                        onchange: move |e| {
+                            sort_order.set(match e.value().as_str() {
+                                "version" => TagSortOrder::Version,
+                                "created" => TagSortOrder::Created,
+                                "size" => TagSortOrder::Size,
+                                _ => TagSortOrder::Name,
+                            });
+                        },
+                        option { value: "name", "Name" }
+                        option { value: "version", "Version" }
+                        option { value: "created", "Newest" }
+                        option { value: "size", "Largest" }
+                    }
+                }
+                
+                if let Err(err) = name_filter() {
+                    p { class: "filter-error", "{err}" }
                 }
                 
                 // Batch actions
@@ -175,83 +180,136 @@ pub fn TagList() -> Element {
                     div {
                         class: "batch-actions",
                         span { "{selected_tags().len()} selected" }
-                        button {
-                            class: "danger small",
-                            onclick: move |_| {
-                                show_delete_confirm.set(true);
-                            },
-                            "Delete Selected"
+                        if let Some(reason) = delete_support.explanation() {
+                            span { class: "delete-disabled", title: "{reason}", "🔒 Delete unavailable" }
+                        } else {
+                            button {
+                                class: "danger small",
+                                // Shared digests can only be checked once every tag is listed
+                                disabled: loading(),
+                                onclick: move |_| {
+                                    show_delete_confirm.set(true);
+                                },
+                                "Delete Selected"
+                            }
                         }
                     }
                 }
                 
                 // Delete confirmation dialog
                 if show_delete_confirm() {
-                    DeleteTagsDialog {
-                        tags_to_delete: selected_tags(),
-                        on_confirm: move |_| {
-                            let tags_to_del = selected_tags();
-                            if let (Some(id), Some(repo_name)) = (
-                                app_state.selected_registry.read().clone(),
-                                app_state.selected_repo.read().clone()
-                            ) {
-                                if let Some(registry) = app_state.get_registry(&id) {
-                                    delete_status.set(Some("Deleting...".to_string()));
-                                    
-                                    spawn(async move {
-                                        match RegistryClient::new(registry.url.clone(), registry.auth.clone()) {
-                                            Ok(client) => {
-                                                let mut deleted = 0;
-                                                let mut errors = Vec::new();
-                                                
-                                                for tag_name in &tags_to_del {
-                                                    // First get the manifest to get the digest
-                                                    match client.get_manifest(&repo_name, tag_name).await {
-                                                        Ok((_, digest)) => {
-                                                            if !digest.is_empty() {
-                                                                match client.delete_manifest(&repo_name, &digest).await {
-                                                                    Ok(_) => deleted += 1,
-                                                                    Err(e) => errors.push(format!("{}: {}", tag_name, e)),
-                                                                }
+                    if let (Some(registry), Some(repo_name)) = (selected_registry.clone(), selected_repo.clone()) {
+                        DeleteTagsDialog {
+                            registry,
+                            repo: repo_name,
+                            tags: tags(),
+                            tags_to_delete: selected_tags(),
+                            on_confirm: move |groups: Vec<DigestGroup>| {
+                                if let (Some(id), Some(repo_name)) = (
+                                    app_state.selected_registry.read().clone(),
+                                    app_state.selected_repo.read().clone()
+                                ) {
+                                    if let Some(registry) = app_state.get_registry(&id) {
+                                        delete_status.set(Some("Deleting...".to_string()));
+                                        
+                                        let page_size = app_state.cache_config.peek().page_size;
+                                        spawn(async move {
+                                            match RegistryClient::for_registry(&registry) {
+                                                Ok(client) => {
+                                                    let mut deleted = 0;
+                                                    let mut errors = Vec::new();
+                                                    
+                                                    // One DELETE per manifest; it removes every tag in the group
+                                                    for group in &groups {
+                                                        match client.delete_manifest(&repo_name, &group.digest).await {
+                                                            Ok(_) => deleted += group.all_tags().len(),
+                                                            // Every other delete would fail the same way
+                                                            Err(e) if matches!(e.kind(), ApiError::MethodNotAllowed(_)) => {
+                                                                app_state.set_delete_support(&registry.id, DeleteSupport::Disabled);
+                                                                errors.push(e.to_string());
+                                                                break;
                                                             }
+                                                            Err(e) => errors.push(format!("{}: {}", group.all_tags().join(", "), e)),
                                                         }
-                                                        Err(e) => errors.push(format!("{}: {}", tag_name, e)),
+                                                    }
+                                                    
+                                                    if errors.is_empty() {
+                                                        delete_status.set(Some(format!("Deleted {} tags", deleted)));
+                                                    } else {
+                                                        delete_status.set(Some(format!("Deleted {}, {} errors", deleted, errors.len())));
+                                                    }
+                                                    
+                                                    // Refresh tags list
+                                                    if load_tags(registry.clone(), repo_name.clone(), page_size, tags).await.is_ok() {
+                                                        enrich_tags(registry, repo_name, tags, enrich_progress, quota_warning, false).await;
                                                     }
                                                 }
-                                                
-                                                if errors.is_empty() {
-                                                    delete_status.set(Some(format!("Deleted {} tags", deleted)));
-                                                } else {
-                                                    delete_status.set(Some(format!("Deleted {}, {} errors", deleted, errors.len())));
-                                                }
-                                                
-                                                // Refresh tags list
-                                                if let Ok(tags_response) = client.get_tags(&repo_name).await {
-                                                    let tag_infos: Vec<TagInfo> = tags_response.tags
-                                                        .unwrap_or_default()
-                                                        .into_iter()
-                                                        .map(|name| TagInfo {
-                                                            name,
-                                                            digest: String::new(),
-                                                            size: 0,
-                                                        })
-                                                        .collect();
-                                                    tags.set(tag_infos);
+                                                Err(e) => {
+                                                    delete_status.set(Some(format!("Error: {}", e)));
                                                 }
                                             }
-                                            Err(e) => {
-                                                delete_status.set(Some(format!("Error: {}", e)));
-                                            }
-                                        }
-                                        selected_tags.set(Vec::new());
-                                    });
+                                            selected_tags.set(Vec::new());
+                                        });
+                                    }
+                                }
+                                show_delete_confirm.set(false);
+                            },
+                            on_cancel: move |_| {
+                                show_delete_confirm.set(false);
+                            },
+                        }
+                    }
+                }
+                
+                // Retention policy dialog
+                if show_retention() {
+                    if let (Some(registry), Some(repo_name)) = (selected_registry.clone(), selected_repo.clone()) {
+                        RetentionDialog {
+                            registry,
+                            repo: repo_name,
+                            tags: tags(),
+                            on_close: move |changed: bool| {
+                                show_retention.set(false);
+                                if changed {
+                                    reload();
                                 }
+                            },
+                        }
+                    }
+                }
+                
+                // Tag details would use up the registry's pull quota
+                if let Some((needed, quota)) = quota_warning() {
+                    div {
+                        class: "quota-warning",
+                        p {
+                            "⚠️ Resolving details for {needed} tags needs a manifest pull per tag, two for multi-arch images, "
+                            "but only {quota.remaining} of {quota.limit} are left on this registry. "
+                            "The quota is shared with everything else using these credentials."
+                        }
+                        div {
+                            class: "form-actions",
+                            button {
+                                class: "secondary small",
+                                onclick: move |_| quota_warning.set(None),
+                                "Skip Details"
                             }
-                            show_delete_confirm.set(false);
-                        },
-                        on_cancel: move |_| {
-                            show_delete_confirm.set(false);
-                        },
+                            button {
+                                class: "danger small",
+                                onclick: move |_| {
+                                    quota_warning.set(None);
+                                    if let (Some(registry), Some(repo_name)) = (
+                                        app_state.selected_registry.peek().clone().and_then(|id| app_state.get_registry(&id)),
+                                        app_state.selected_repo.peek().clone(),
+                                    ) {
+                                        spawn(async move {
+                                            enrich_tags(registry, repo_name, tags, enrich_progress, quota_warning, true).await;
+                                        });
+                                    }
+                                },
+                                "Resolve Anyway"
+                            }
+                        }
                     }
                 }
                 
@@ -268,12 +326,12 @@ pub fn TagList() -> Element {
                     }
                 }
                 
-                if loading() {
+                if loading() && tags.read().is_empty() {
                     p { class: "loading", "Loading..." }
                 } else if let Some(err) = error() {
                     div {
                         class: "error-box",
-                        p { class: "error", "{err}" }
+                        ErrorDisplay { error: err }
                         button {
                             class: "secondary small",
                             onclick: refresh,
@@ -290,6 +348,11 @@ pub fn TagList() -> Element {
                         }
                     }
                 } else {
+                    if loading() {
+                        p { class: "loading-more", "Loaded {tags.read().len()} tags, loading more..." }
+                    } else if let Some((done, total)) = enrich_progress() {
+                        p { class: "loading-more", "Resolving tag details {done} / {total}..." }
+                    }
                     div {
                         class: "list",
                         for tag in filtered() {
@@ -310,9 +373,106 @@ pub fn TagList() -> Element {
                         }
                     }
                 }
+                
+                if let (Some(registry), Some(repo_name)) = (selected_registry.clone(), selected_repo.clone()) {
+                    CommandPanel {
+                        registry,
+                        targets: tag_command_targets(&repo_name, selected_tag.as_deref()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Commands offered for the repository, and the selected tag if any
+fn tag_command_targets(repo: &str, tag: Option<&str>) -> Vec<CommandTarget> {
+    let mut targets = vec![CommandTarget::Tags { repo: repo.to_string() }];
+    if let Some(tag) = tag {
+        targets.push(CommandTarget::Manifest {
+            repo: repo.to_string(),
+            reference: tag.to_string(),
+        });
+    }
+    targets.push(CommandTarget::Catalog);
+    targets
+}
+
+/// Load all tag pages, showing partial results as each page arrives
+async fn load_tags(
+    registry: RegistryConfig,
+    repo: String,
+    page_size: usize,
+    mut tags: Signal<Vec<TagInfo>>,
+) -> Result<(), ApiError> {
+    let client = RegistryClient::for_registry(&registry)?;
+    
+    let mut first_page = true;
+    client
+        .list_all_tags_with(&repo, page_size, |page| {
+            let infos = page.iter().cloned().map(TagInfo::new);
+            // Replace stale results only once the first page is in
+            if first_page {
+                tags.set(infos.collect());
+                first_page = false;
+            } else {
+                tags.write().extend(infos);
             }
+        })
+        .await
+}
+
+/// Resolve digest, size and creation date of every tag, updating rows as results arrive
+///
+/// Stops with `quota_warning` set when the registry's pull quota would run out,
+/// unless `force` is given.
+async fn enrich_tags(
+    registry: RegistryConfig,
+    repo: String,
+    mut tags: Signal<Vec<TagInfo>>,
+    mut progress: Signal<Option<(usize, usize)>>,
+    mut quota_warning: Signal<Option<(usize, RateLimitQuota)>>,
+    force: bool,
+) {
+    let Ok(client) = RegistryClient::for_registry(&registry) else {
+        return;
+    };
+    
+    let pending: Vec<String> = tags
+        .peek()
+        .iter()
+        .filter(|t| !t.is_enriched())
+        .map(|t| t.name.clone())
+        .collect();
+    let total = pending.len();
+    if total == 0 {
+        return;
+    }
+    
+    if !force {
+        if let Some(quota) = client.quota_shortfall(&repo, &pending[0], total).await {
+            quota_warning.set(Some((total, quota)));
+            return;
         }
     }
+    quota_warning.set(None);
+    progress.set(Some((0, total)));
+    
+    let mut done = 0;
+    client
+        .get_tag_infos_with(&repo, pending, TAG_INFO_CONCURRENCY, |_, result| {
+            done += 1;
+            // Tags that fail to resolve keep their name-only row
+            if let Ok(info) = result {
+                if let Some(row) = tags.write().iter_mut().find(|t| t.name == info.name) {
+                    *row = info;
+                }
+            }
+            progress.set(Some((done, total)));
+        })
+        .await;
+    
+    progress.set(None);
 }
 
 /// Single tag item
@@ -327,6 +487,7 @@ fn TagItem(
     let name = tag.name.clone();
     let name_toggle = tag.name.clone();
     let size_str = if tag.size > 0 { format_size(tag.size) } else { String::new() };
+    let created_str = tag.created.as_deref().map(format_created).unwrap_or_default();
     let digest_short = if tag.digest.len() > 19 {
         format!("{}...", &tag.digest[..19])
     } else if tag.digest.is_empty() {
@@ -358,20 +519,96 @@ fn TagItem(
                 if !size_str.is_empty() {
                     span { class: "tag-size", "{size_str}" }
                 }
+                if !created_str.is_empty() {
+                    span { class: "tag-created", title: "{tag.created.clone().unwrap_or_default()}", "{created_str}" }
+                }
             }
         }
     }
 }
 
+/// Format an RFC 3339 creation time for display
+fn format_created(created: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(created)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| created.to_string())
+}
 
 /// Delete tags confirmation dialog
+///
+/// Resolves the digest of every tag in the repository first, so that tags sharing a
+/// manifest with a selected tag are listed before anything is deleted.
 #[component]
 fn DeleteTagsDialog(
+    registry: RegistryConfig,
+    repo: String,
+    tags: Vec<TagInfo>,
     tags_to_delete: Vec<String>,
-    on_confirm: EventHandler<()>,
+    on_confirm: EventHandler<Vec<DigestGroup>>,
     on_cancel: EventHandler<()>,
 ) -> Element {
+    let pending: Vec<String> = tags
+        .iter()
+        .filter(|t| t.digest.is_empty())
+        .map(|t| t.name.clone())
+        .collect();
+    let pending_total = pending.len();
+    
+    let mut known = use_signal(|| tags.clone());
+    let mut checking = use_signal(|| (pending_total > 0).then_some((0usize, pending_total)));
+    let mut unresolved = use_signal(Vec::<(String, String)>::new);
+    
+    // Digests the tag list has not resolved yet are fetched with HEAD requests
+    use_future(move || {
+        let registry = registry.clone();
+        let repo = repo.clone();
+        let pending = pending.clone();
+        async move {
+            if pending.is_empty() {
+                return;
+            }
+            let client = match RegistryClient::for_registry(&registry) {
+                Ok(client) => client,
+                Err(e) => {
+                    unresolved.set(pending.into_iter().map(|name| (name, e.to_string())).collect());
+                    checking.set(None);
+                    return;
+                }
+            };
+            
+            let total = pending.len();
+            let mut done = 0;
+            client
+                .head_manifests_with(&repo, pending, TAG_INFO_CONCURRENCY, |name, result| {
+                    done += 1;
+                    match result {
+                        Ok(info) => {
+                            if let Some(row) = known.write().iter_mut().find(|t| t.name == name) {
+                                row.digest = info.digest;
+                            }
+                        }
+                        Err(e) => unresolved.write().push((name, e.to_string())),
+                    }
+                    checking.set(Some((done, total)));
+                })
+                .await;
+            checking.set(None);
+        }
+    });
+    
     let count = tags_to_delete.len();
+    let groups = group_by_digest(&known.read(), &tags_to_delete);
+    let collateral: Vec<DigestGroup> = groups.iter().filter(|g| !g.collateral.is_empty()).cloned().collect();
+    let collateral_count: usize = collateral.iter().map(|g| g.collateral.len()).sum();
+    let skipped: Vec<(String, String)> = unresolved
+        .read()
+        .iter()
+        .filter(|(name, _)| tags_to_delete.contains(name))
+        .cloned()
+        .collect();
+    let unchecked = unresolved.read().len() - skipped.len();
+    let delete_total: usize = groups.iter().map(|g| g.all_tags().len()).sum();
+    let can_delete = checking().is_none() && !groups.is_empty();
     
     rsx! {
         div {
@@ -401,6 +638,45 @@ fn DeleteTagsDialog(
                         }
                     }
                     
+                    if let Some((done, total)) = checking() {
+                        p { class: "loading-more", "Checking tags for shared digests {done} / {total}..." }
+                    } else {
+                        if !collateral.is_empty() {
+                            div {
+                                class: "shared-digest-warning",
+                                p {
+                                    class: "warning",
+                                    "⚠️ {collateral_count} other tag(s) point at the same manifest as a selected tag and will also be deleted:"
+                                }
+                                for group in collateral.iter() {
+                                    div {
+                                        key: "{group.digest}",
+                                        class: "shared-digest-group",
+                                        span { class: "tag-digest", title: "{group.digest}", "{short_digest(&group.digest)}" }
+                                        for tag in group.collateral.iter() {
+                                            span { class: "tag-badge collateral", "{tag}" }
+                                        }
+                                        span { class: "shared-with", "shared with {group.selected.join(\", \")}" }
+                                    }
+                                }
+                            }
+                        }
+                        
+                        if !skipped.is_empty() {
+                            div {
+                                class: "error-list",
+                                p { class: "error", "{skipped.len()} selected tag(s) could not be resolved and will be skipped:" }
+                                for (name, err) in skipped.iter() {
+                                    p { class: "error-item", "{name}: {err}" }
+                                }
+                            }
+                        }
+                        
+                        if unchecked > 0 {
+                            p { class: "hint", "{unchecked} other tag(s) could not be checked and may share a manifest with the selection." }
+                        }
+                    }
+                    
                     p { class: "warning", "⚠️ This action cannot be undone." }
                     
                     div {
@@ -412,8 +688,13 @@ fn DeleteTagsDialog(
                         }
                         button {
                             class: "danger",
-                            onclick: move |_| on_confirm.call(()),
-                            "Delete"
+                            disabled: !can_delete,
+                            onclick: move |_| on_confirm.call(groups.clone()),
+                            if collateral_count > 0 {
+                                "Delete Anyway ({delete_total} tags)"
+                            } else {
+                                "Delete"
+                            }
                         }
                     }
                 }
@@ -421,3 +702,12 @@ fn DeleteTagsDialog(
         }
     }
 }
+
+/// Shorten a digest for display
+fn short_digest(digest: &str) -> String {
+    if digest.len() > 19 {
+        format!("{}...", &digest[..19])
+    } else {
+        digest.to_string()
+    }
+}
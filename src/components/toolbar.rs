@@ -1,12 +1,21 @@
 //! Toolbar component
 
 use dioxus::prelude::*;
+use std::time::Duration;
 use crate::state::AppState;
+use crate::api::{retry_waits, RetryWait};
 use crate::models::Theme;
+use crate::utils::sleep;
 
 /// Application toolbar component
 #[component]
-pub fn Toolbar(show_settings: Signal<bool>) -> Element {
+pub fn Toolbar(
+    show_settings: Signal<bool>,
+    show_network_log: Signal<bool>,
+    can_lock: bool,
+    lock_pending: bool,
+    on_lock: EventHandler<()>,
+) -> Element {
     let mut app_state = use_context::<AppState>();
     let theme = app_state.theme;
     
@@ -16,6 +25,8 @@ pub fn Toolbar(show_settings: Signal<bool>) -> Element {
             
             h1 { "Docker Registry Manager" }
             
+            RetryStatus {}
+            
             div {
                 class: "toolbar-actions",
                 
@@ -48,6 +59,14 @@ pub fn Toolbar(show_settings: Signal<bool>) -> Element {
                     }
                 }
                 
+                // Network log toggle
+                button {
+                    class: if show_network_log() { "btn-icon active" } else { "btn-icon" },
+                    title: "Network log",
+                    onclick: move |_| show_network_log.set(!show_network_log()),
+                    "📡"
+                }
+                
                 // Settings toggle
                 button {
                     class: if show_settings() { "btn-icon active" } else { "btn-icon" },
@@ -55,6 +74,67 @@ pub fn Toolbar(show_settings: Signal<bool>) -> Element {
                     onclick: move |_| show_settings.set(!show_settings()),
                     "⚙️"
                 }
+                
+                // Lock: drop decrypted credentials and ask for the password again
+                if can_lock {
+                    button {
+                        class: if lock_pending { "btn-icon active" } else { "btn-icon" },
+                        title: if lock_pending { "Locking once the running deletion finishes" } else { "Lock" },
+                        onclick: move |_| on_lock.call(()),
+                        "🔒"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Countdown for requests waiting to be retried after throttling or a transient failure
+#[component]
+fn RetryStatus() -> Element {
+    let app_state = use_context::<AppState>();
+    let mut waits = use_signal(Vec::<RetryWait>::new);
+    
+    // Retries are tracked by the API client, outside of the app state
+    use_future(move || async move {
+        loop {
+            let current = retry_waits();
+            if *waits.peek() != current || !current.is_empty() {
+                waits.set(current);
+            }
+            sleep(Duration::from_millis(250)).await;
+        }
+    });
+    
+    let current = waits();
+    let Some(next) = current.first() else {
+        return rsx! {};
+    };
+    
+    let registry = app_state
+        .registries
+        .read()
+        .iter()
+        .find(|r| r.url.trim_end_matches('/') == next.registry)
+        .map(|r| r.name.clone())
+        .unwrap_or_else(|| next.registry.clone());
+    let reason = match next.status {
+        Some(429) => "is rate limiting requests".to_string(),
+        Some(status) => format!("answered {}", status),
+        None => "is unreachable".to_string(),
+    };
+    let others = current.len() - 1;
+    
+    rsx! {
+        div {
+            class: "retry-status",
+            title: "Requests are retried automatically up to the limit set for each registry",
+            span { class: "retry-icon", "⏳" }
+            span {
+                "{registry} {reason}, retrying in {next.seconds_left()}s (attempt {next.attempt} of {next.max_retries})"
+            }
+            if others > 0 {
+                span { class: "retry-more", "+{others} more waiting" }
             }
         }
     }
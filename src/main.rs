@@ -3,6 +3,8 @@
 use base64::Engine;
 use dioxus::prelude::*;
 use docker_registry_manager::components::App;
+#[cfg(not(target_arch = "wasm32"))]
+use docker_registry_manager::storage::{get_storage, CredentialBackend};
 
 /// Embedded CSS styles
 const MAIN_CSS: &str = include_str!("../assets/main.css");
@@ -67,8 +69,9 @@ fn Root() -> Element {
     let mut is_ready = use_signal(|| {
         #[cfg(target_arch = "wasm32")]
         { true }
+        // Keychain-backed credentials need no master password
         #[cfg(not(target_arch = "wasm32"))]
-        { false }
+        { get_storage().credential_backend() == CredentialBackend::Keychain }
     });
     
     // Minimal theme detection for the prompt (defaults to system)
@@ -85,7 +88,7 @@ fn Root() -> Element {
             "data-theme": "{theme_class}",
             
             if is_ready() {
-                App {}
+                App { on_lock: move |_| is_ready.set(false) }
             } else {
                 docker_registry_manager::components::EncryptionPrompt {
                     on_ready: move |_| is_ready.set(true)
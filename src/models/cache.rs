@@ -9,6 +9,16 @@ pub struct CacheConfig {
     pub refresh_interval: u64,
     /// Maximum cache age in seconds
     pub max_age: u64,
+    /// Number of entries requested per page when listing repositories and tags
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+}
+
+/// Page size used when none is configured
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+fn default_page_size() -> usize {
+    DEFAULT_PAGE_SIZE
 }
 
 impl Default for CacheConfig {
@@ -16,6 +26,7 @@ impl Default for CacheConfig {
         Self {
             refresh_interval: 0,
             max_age: 3600, // 1 hour
+            page_size: DEFAULT_PAGE_SIZE,
         }
     }
 }
@@ -0,0 +1,86 @@
+//! Bulk deletion result models
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of deleting a single tag
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeletionStatus {
+    Deleted,
+    Failed,
+    /// Not attempted because the deletion was cancelled
+    Cancelled,
+}
+
+impl DeletionStatus {
+    /// Lowercase label used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeletionStatus::Deleted => "deleted",
+            DeletionStatus::Failed => "failed",
+            DeletionStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Per-tag entry of a deletion report
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeletionEntry {
+    pub tag: String,
+    /// Manifest digest, empty when it could not be resolved
+    pub digest: String,
+    pub status: DeletionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of DELETE requests made, including retries; 0 if the digest was not resolved
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl DeletionEntry {
+    /// Entry for a tag that was never attempted
+    pub fn cancelled(tag: String) -> Self {
+        Self {
+            tag,
+            digest: String::new(),
+            status: DeletionStatus::Cancelled,
+            error: None,
+            attempts: 0,
+        }
+    }
+}
+
+/// Structured result of a bulk deletion
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeletionResult {
+    pub entries: Vec<DeletionEntry>,
+}
+
+impl DeletionResult {
+    fn count(&self, status: DeletionStatus) -> usize {
+        self.entries.iter().filter(|e| e.status == status).count()
+    }
+    
+    /// Number of deleted tags
+    pub fn deleted(&self) -> usize {
+        self.count(DeletionStatus::Deleted)
+    }
+    
+    /// Number of tags that failed to delete
+    pub fn failed(&self) -> usize {
+        self.count(DeletionStatus::Failed)
+    }
+    
+    /// Number of tags skipped after cancelling
+    pub fn cancelled(&self) -> usize {
+        self.count(DeletionStatus::Cancelled)
+    }
+    
+    /// Failed entries formatted as `tag: error`
+    pub fn errors(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == DeletionStatus::Failed)
+            .map(|e| format!("{}: {}", e.tag, e.error.as_deref().unwrap_or("Unknown error")))
+            .collect()
+    }
+}
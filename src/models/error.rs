@@ -23,26 +23,146 @@ pub enum ErrorCode {
     ValidationError,
     StorageError,
     ParseError,
+    Unsupported,
 }
 
 /// Detailed error information for debugging
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ErrorDetails {
     pub status_code: Option<u16>,
     pub response_headers: Option<Vec<(String, String)>>,
     pub response_body: Option<String>,
     pub curl_command: Option<String>,
+    /// Errors reported by the registry in the response body
+    #[serde(default)]
+    pub registry_errors: Vec<RegistryError>,
+}
+
+/// Error code from the distribution spec error envelope
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum RegistryErrorCode {
+    BlobUnknown,
+    BlobUploadInvalid,
+    BlobUploadUnknown,
+    DigestInvalid,
+    ManifestBlobUnknown,
+    ManifestInvalid,
+    ManifestUnknown,
+    ManifestUnverified,
+    NameInvalid,
+    NameUnknown,
+    SizeInvalid,
+    TagInvalid,
+    Unauthorized,
+    Denied,
+    Unsupported,
+    TooManyRequests,
+    /// Code not defined by the spec (registries add their own)
+    Other(String),
+}
+
+impl RegistryErrorCode {
+    /// Code as sent on the wire, e.g. `MANIFEST_UNKNOWN`
+    pub fn as_str(&self) -> &str {
+        match self {
+            RegistryErrorCode::BlobUnknown => "BLOB_UNKNOWN",
+            RegistryErrorCode::BlobUploadInvalid => "BLOB_UPLOAD_INVALID",
+            RegistryErrorCode::BlobUploadUnknown => "BLOB_UPLOAD_UNKNOWN",
+            RegistryErrorCode::DigestInvalid => "DIGEST_INVALID",
+            RegistryErrorCode::ManifestBlobUnknown => "MANIFEST_BLOB_UNKNOWN",
+            RegistryErrorCode::ManifestInvalid => "MANIFEST_INVALID",
+            RegistryErrorCode::ManifestUnknown => "MANIFEST_UNKNOWN",
+            RegistryErrorCode::ManifestUnverified => "MANIFEST_UNVERIFIED",
+            RegistryErrorCode::NameInvalid => "NAME_INVALID",
+            RegistryErrorCode::NameUnknown => "NAME_UNKNOWN",
+            RegistryErrorCode::SizeInvalid => "SIZE_INVALID",
+            RegistryErrorCode::TagInvalid => "TAG_INVALID",
+            RegistryErrorCode::Unauthorized => "UNAUTHORIZED",
+            RegistryErrorCode::Denied => "DENIED",
+            RegistryErrorCode::Unsupported => "UNSUPPORTED",
+            RegistryErrorCode::TooManyRequests => "TOOMANYREQUESTS",
+            RegistryErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for RegistryErrorCode {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "BLOB_UNKNOWN" => RegistryErrorCode::BlobUnknown,
+            "BLOB_UPLOAD_INVALID" => RegistryErrorCode::BlobUploadInvalid,
+            "BLOB_UPLOAD_UNKNOWN" => RegistryErrorCode::BlobUploadUnknown,
+            "DIGEST_INVALID" => RegistryErrorCode::DigestInvalid,
+            "MANIFEST_BLOB_UNKNOWN" => RegistryErrorCode::ManifestBlobUnknown,
+            "MANIFEST_INVALID" => RegistryErrorCode::ManifestInvalid,
+            "MANIFEST_UNKNOWN" => RegistryErrorCode::ManifestUnknown,
+            "MANIFEST_UNVERIFIED" => RegistryErrorCode::ManifestUnverified,
+            "NAME_INVALID" => RegistryErrorCode::NameInvalid,
+            "NAME_UNKNOWN" => RegistryErrorCode::NameUnknown,
+            "SIZE_INVALID" => RegistryErrorCode::SizeInvalid,
+            "TAG_INVALID" => RegistryErrorCode::TagInvalid,
+            "UNAUTHORIZED" => RegistryErrorCode::Unauthorized,
+            "DENIED" => RegistryErrorCode::Denied,
+            "UNSUPPORTED" => RegistryErrorCode::Unsupported,
+            "TOOMANYREQUESTS" => RegistryErrorCode::TooManyRequests,
+            _ => RegistryErrorCode::Other(code),
+        }
+    }
+}
+
+impl From<RegistryErrorCode> for String {
+    fn from(code: RegistryErrorCode) -> Self {
+        code.as_str().to_string()
+    }
+}
+
+/// Single entry of a registry error body
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegistryError {
+    pub code: RegistryErrorCode,
+    #[serde(default)]
+    pub message: String,
+    /// Unstructured, code-specific detail
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<serde_json::Value>,
+}
+
+/// Error body returned by registries: `{"errors":[{"code":...,"message":...,"detail":...}]}`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegistryErrorEnvelope {
+    #[serde(default)]
+    pub errors: Vec<RegistryError>,
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.code.as_str())
+        } else {
+            write!(f, "{}: {}", self.code.as_str(), self.message)
+        }
+    }
 }
 
 /// API request information for debugging
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct ApiRequestInfo {
+    /// Sequence number in the network log
+    pub id: u64,
+    /// Unix time in milliseconds when the request was sent
+    pub started_at: u64,
+    pub duration_ms: u64,
     pub method: String,
     pub url: String,
+    /// Request headers, credentials masked
     pub headers: Vec<(String, String)>,
     pub status_code: Option<u16>,
     pub response_headers: Option<Vec<(String, String)>>,
+    /// Response body, truncated
     pub response_body: Option<String>,
+    /// Transport error when no response arrived
+    pub error: Option<String>,
     pub curl_command: String,
 }
 
@@ -1,6 +1,7 @@
 //! Docker manifest and related models
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Catalog response from /v2/_catalog
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -16,6 +17,8 @@ pub struct TagsResponse {
     pub name: String,
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    #[serde(skip)]
+    pub next_page: Option<String>,
 }
 
 /// Docker manifest - supports multiple schema versions
@@ -24,6 +27,8 @@ pub struct TagsResponse {
 pub enum Manifest {
     V2(ManifestV2),
     OCI(OciManifest),
+    ManifestList(ManifestList),
+    OciIndex(OciIndex),
     V1(ManifestV1),
 }
 
@@ -49,6 +54,58 @@ pub struct OciManifest {
     pub layers: Vec<Descriptor>,
 }
 
+/// Docker manifest list (multi-arch image)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ManifestList {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: i32,
+    #[serde(rename = "mediaType")]
+    pub media_type: String,
+    pub manifests: Vec<PlatformManifest>,
+}
+
+/// OCI image index (multi-arch image)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OciIndex {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: i32,
+    #[serde(rename = "mediaType", default)]
+    pub media_type: Option<String>,
+    pub manifests: Vec<PlatformManifest>,
+}
+
+/// Child manifest entry of a manifest list or image index
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlatformManifest {
+    #[serde(rename = "mediaType")]
+    pub media_type: String,
+    pub size: u64,
+    pub digest: String,
+    #[serde(default)]
+    pub platform: Option<Platform>,
+}
+
+/// Platform a child manifest was built for
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Platform {
+    pub architecture: String,
+    pub os: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    #[serde(rename = "os.version", default, skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+}
+
+impl Platform {
+    /// Format as `os/architecture[/variant]`, e.g. `linux/arm64/v8`
+    pub fn display_name(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{}/{}/{}", self.os, self.architecture, variant),
+            None => format!("{}/{}", self.os, self.architecture),
+        }
+    }
+}
+
 /// Docker manifest schema version 1 (legacy)
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ManifestV1 {
@@ -97,6 +154,41 @@ pub struct ImageConfig {
     pub author: Option<String>,
     #[serde(default)]
     pub history: Option<Vec<HistoryEntry>>,
+    /// Runtime configuration (env, entrypoint, cmd, ...)
+    #[serde(default)]
+    pub config: Option<ContainerConfig>,
+    /// Uncompressed layer digests
+    #[serde(default)]
+    pub rootfs: Option<RootFs>,
+}
+
+/// Container runtime configuration from the image config
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerConfig {
+    #[serde(rename = "Env", default)]
+    pub env: Option<Vec<String>>,
+    #[serde(rename = "Entrypoint", default)]
+    pub entrypoint: Option<Vec<String>>,
+    #[serde(rename = "Cmd", default)]
+    pub cmd: Option<Vec<String>>,
+    #[serde(rename = "Labels", default)]
+    pub labels: Option<BTreeMap<String, String>>,
+    /// Keys are `port/protocol`, values are always empty objects
+    #[serde(rename = "ExposedPorts", default)]
+    pub exposed_ports: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(rename = "WorkingDir", default)]
+    pub working_dir: Option<String>,
+    #[serde(rename = "User", default)]
+    pub user: Option<String>,
+}
+
+/// Root filesystem description from the image config
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RootFs {
+    #[serde(rename = "type")]
+    pub fs_type: String,
+    #[serde(default)]
+    pub diff_ids: Vec<String>,
 }
 
 /// History entry from image config
@@ -119,12 +211,48 @@ pub struct BlobInfo {
     pub media_type: Option<String>,
 }
 
+/// Manifest information from a HEAD request
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ManifestInfo {
+    pub digest: String,
+    #[serde(rename = "mediaType")]
+    pub media_type: String,
+    pub size: u64,
+}
+
+impl ManifestInfo {
+    /// Check if the manifest is a manifest list or image index
+    pub fn is_index(&self) -> bool {
+        self.media_type.contains("manifest.list") || self.media_type.contains("image.index")
+    }
+}
+
 /// Tag information with digest and size
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TagInfo {
     pub name: String,
     pub digest: String,
     pub size: u64,
+    /// Image creation time from the config blob (RFC 3339)
+    #[serde(default)]
+    pub created: Option<String>,
+}
+
+impl TagInfo {
+    /// Create tag info for a tag whose digest and size are not known yet
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            digest: String::new(),
+            size: 0,
+            created: None,
+        }
+    }
+    
+    /// Check if digest and size have been resolved
+    pub fn is_enriched(&self) -> bool {
+        !self.digest.is_empty()
+    }
 }
 
 impl Manifest {
@@ -133,16 +261,63 @@ impl Manifest {
         match self {
             Manifest::V2(m) => &m.media_type,
             Manifest::OCI(m) => m.media_type.as_deref().unwrap_or("application/vnd.oci.image.manifest.v1+json"),
+            Manifest::ManifestList(m) => &m.media_type,
+            Manifest::OciIndex(m) => m.media_type.as_deref().unwrap_or("application/vnd.oci.image.index.v1+json"),
             Manifest::V1(_) => "application/vnd.docker.distribution.manifest.v1+json",
         }
     }
+    
+    /// Check if this is a manifest list or image index
+    pub fn is_index(&self) -> bool {
+        matches!(self, Manifest::ManifestList(_) | Manifest::OciIndex(_))
+    }
+    
+    /// Get the per-platform child manifests of a list or index
+    pub fn manifests(&self) -> Vec<&PlatformManifest> {
+        match self {
+            Manifest::ManifestList(m) => m.manifests.iter().collect(),
+            Manifest::OciIndex(m) => m.manifests.iter().collect(),
+            _ => vec![],
+        }
+    }
 
+    /// Pick the child manifest that best represents a multi-arch image
+    ///
+    /// Prefers linux/amd64 and falls back to the first real platform entry.
+    pub fn default_platform_manifest(&self) -> Option<&PlatformManifest> {
+        let manifests = self.manifests();
+        manifests
+            .iter()
+            .find(|m| {
+                m.platform
+                    .as_ref()
+                    .map(|p| p.os == "linux" && p.architecture == "amd64")
+                    .unwrap_or(false)
+            })
+            .or_else(|| {
+                // Skip attestation manifests, which use the "unknown" platform
+                manifests.iter().find(|m| {
+                    m.platform.as_ref().map(|p| p.os != "unknown").unwrap_or(true)
+                })
+            })
+            .copied()
+    }
+    
+    /// Get the config blob descriptor of a single-platform manifest
+    pub fn config(&self) -> Option<&Descriptor> {
+        match self {
+            Manifest::V2(m) => Some(&m.config),
+            Manifest::OCI(m) => Some(&m.config),
+            _ => None,
+        }
+    }
+    
     /// Get the layers from the manifest
     pub fn layers(&self) -> Vec<&Descriptor> {
         match self {
             Manifest::V2(m) => m.layers.iter().collect(),
             Manifest::OCI(m) => m.layers.iter().collect(),
-            Manifest::V1(_) => vec![],
+            Manifest::ManifestList(_) | Manifest::OciIndex(_) | Manifest::V1(_) => vec![],
         }
     }
 
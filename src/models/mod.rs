@@ -4,8 +4,12 @@ mod registry;
 mod manifest;
 mod cache;
 mod error;
+mod retention;
+mod deletion;
 
 pub use registry::*;
 pub use manifest::*;
 pub use cache::*;
 pub use error::*;
+pub use retention::*;
+pub use deletion::*;
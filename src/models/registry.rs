@@ -1,6 +1,7 @@
 //! Registry configuration models
 
 use serde::{Deserialize, Serialize};
+use crate::storage::CredentialBackend;
 
 /// Registry configuration for connecting to a Docker Registry
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -13,6 +14,12 @@ pub struct RegistryConfig {
     pub url: String,
     /// Authentication configuration
     pub auth: AuthConfig,
+    /// TLS and transport options
+    #[serde(default = "TlsOptions::legacy")]
+    pub tls: TlsOptions,
+    /// How often a throttled or failed GET/HEAD request is retried (0 disables retries)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
     /// Connection status (not serialized)
     #[serde(skip)]
     pub status: ConnectionStatus,
@@ -46,16 +53,40 @@ pub enum AuthConfig {
         cert_path: String,
         key_path: String,
     },
+    /// Docker identity token, an OAuth2 refresh token exchanged at the token realm
+    IdentityToken {
+        #[serde(default)]
+        token: String,
+        /// Encrypted token for storage
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        encrypted_token: String,
+    },
 }
 
+/// Username the docker CLI and its credential helpers use to mark an identity token
+///
+/// Only read when importing; such credentials become `AuthConfig::IdentityToken`.
+pub const IDENTITY_TOKEN_USERNAME: &str = "<token>";
+
 impl AuthConfig {
     /// Encrypt sensitive fields before saving
-    pub fn encrypt_for_storage(&self) -> Result<Self, String> {
+    ///
+    /// With the keychain backend the secret is stored under `entry` in the OS
+    /// keychain and only a reference to it is kept in the config.
+    pub fn encrypt_for_storage(&self, backend: CredentialBackend, entry: &str) -> Result<Self, String> {
         use crate::storage::encryption::encrypt_string;
+        use crate::storage::secrets::store_secret;
+        
+        let protect = |secret: &str| match backend {
+            CredentialBackend::Encrypted => encrypt_string(secret),
+            CredentialBackend::Keychain if secret.is_empty() => Ok(String::new()),
+            CredentialBackend::Keychain => store_secret(entry, secret),
+        }
+        .map_err(|e| e.to_string());
         
         match self {
             AuthConfig::BasicAuth { username, password, .. } => {
-                let encrypted = encrypt_string(password).map_err(|e| e.to_string())?;
+                let encrypted = protect(password)?;
                 Ok(AuthConfig::BasicAuth {
                     username: username.clone(),
                     password: String::new(), // Clear plaintext
@@ -63,25 +94,68 @@ impl AuthConfig {
                 })
             }
             AuthConfig::BearerToken { token, .. } => {
-                let encrypted = encrypt_string(token).map_err(|e| e.to_string())?;
+                let encrypted = protect(token)?;
                 Ok(AuthConfig::BearerToken {
                     token: String::new(), // Clear plaintext
                     encrypted_token: encrypted,
                 })
             }
+            AuthConfig::IdentityToken { token, .. } => {
+                let encrypted = protect(token)?;
+                Ok(AuthConfig::IdentityToken {
+                    token: String::new(), // Clear plaintext
+                    encrypted_token: encrypted,
+                })
+            }
             other => Ok(other.clone()),
         }
     }
     
-    /// Decrypt sensitive fields after loading
+    /// Overwrite plaintext secrets in place before the config is dropped
+    pub fn zeroize_secrets(&mut self) {
+        use zeroize::Zeroize;
+        
+        match self {
+            AuthConfig::BasicAuth { password, .. } => password.zeroize(),
+            AuthConfig::BearerToken { token, .. } | AuthConfig::IdentityToken { token, .. } => token.zeroize(),
+            _ => {}
+        }
+    }
+    
+    /// Whether stored secrets are still in the legacy encryption format
+    pub fn has_legacy_secrets(&self) -> bool {
+        use crate::storage::encryption::is_legacy_ciphertext;
+        use crate::storage::secrets::is_secret_reference;
+        
+        let is_legacy = |stored: &str| is_legacy_ciphertext(stored) && !is_secret_reference(stored);
+        match self {
+            AuthConfig::BasicAuth { encrypted_password, .. } => is_legacy(encrypted_password),
+            AuthConfig::BearerToken { encrypted_token, .. } | AuthConfig::IdentityToken { encrypted_token, .. } => {
+                is_legacy(encrypted_token)
+            }
+            _ => false,
+        }
+    }
+    
+    /// Decrypt sensitive fields after loading, reading keychain references from the keychain
     pub fn decrypt_from_storage(&self) -> Result<Self, String> {
         use crate::storage::encryption::decrypt_string;
+        use crate::storage::secrets::{is_secret_reference, load_secret};
+        
+        let reveal = |stored: &str| {
+            let revealed = if is_secret_reference(stored) {
+                load_secret(stored)
+            } else {
+                decrypt_string(stored)
+            };
+            revealed.map_err(|e| e.to_string())
+        };
         
         match self {
             AuthConfig::BasicAuth { username, password, encrypted_password } => {
                 // If we have encrypted password, decrypt it
                 let decrypted = if !encrypted_password.is_empty() {
-                    decrypt_string(encrypted_password).map_err(|e| e.to_string())?
+                    reveal(encrypted_password)?
                 } else {
                     password.clone()
                 };
@@ -93,7 +167,7 @@ impl AuthConfig {
             }
             AuthConfig::BearerToken { token, encrypted_token } => {
                 let decrypted = if !encrypted_token.is_empty() {
-                    decrypt_string(encrypted_token).map_err(|e| e.to_string())?
+                    reveal(encrypted_token)?
                 } else {
                     token.clone()
                 };
@@ -102,11 +176,53 @@ impl AuthConfig {
                     encrypted_token: String::new(),
                 })
             }
+            AuthConfig::IdentityToken { token, encrypted_token } => {
+                let decrypted = if !encrypted_token.is_empty() {
+                    reveal(encrypted_token)?
+                } else {
+                    token.clone()
+                };
+                Ok(AuthConfig::IdentityToken {
+                    token: decrypted,
+                    encrypted_token: String::new(),
+                })
+            }
             other => Ok(other.clone()),
         }
     }
 }
 
+/// Automatic retries of GET/HEAD requests unless configured otherwise
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+pub(crate) fn default_max_retries() -> u32 {
+    DEFAULT_MAX_RETRIES
+}
+
+/// Per-registry TLS options
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TlsOptions {
+    /// Extra PEM CA bundle trusted in addition to the system roots (Desktop only)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub ca_bundle_path: String,
+    /// Skip server certificate verification (insecure, Desktop only)
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
+    /// Allow connecting over plain HTTP
+    #[serde(default)]
+    pub allow_http: bool,
+}
+
+impl TlsOptions {
+    /// Options for configs saved before TLS settings existed, which could use http:// URLs freely
+    pub fn legacy() -> Self {
+        Self {
+            allow_http: true,
+            ..Self::default()
+        }
+    }
+}
+
 /// Connection status for a registry
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum ConnectionStatus {
@@ -117,6 +233,92 @@ pub enum ConnectionStatus {
     Error(String),
 }
 
+/// Whether a registry accepts manifest deletes, as found by probing it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeleteSupport {
+    #[default]
+    Unknown,
+    Enabled,
+    /// The registry answers 405 (e.g. `REGISTRY_STORAGE_DELETE_ENABLED` is off)
+    Disabled,
+    /// The configured credentials are not allowed to delete
+    Denied,
+}
+
+impl DeleteSupport {
+    /// Whether delete actions should be offered
+    pub fn allows_delete(&self) -> bool {
+        matches!(self, DeleteSupport::Unknown | DeleteSupport::Enabled)
+    }
+    
+    /// Explanation shown in place of delete actions
+    pub fn explanation(&self) -> Option<&'static str> {
+        match self {
+            DeleteSupport::Disabled => Some(
+                "Deletion is disabled on this registry. Set REGISTRY_STORAGE_DELETE_ENABLED=true on the server to enable it.",
+            ),
+            DeleteSupport::Denied => Some("These credentials are not allowed to delete from this registry."),
+            DeleteSupport::Unknown | DeleteSupport::Enabled => None,
+        }
+    }
+}
+
+/// Pull quota reported by the registry (Docker Hub `ratelimit-*` headers)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    /// Requests allowed per window
+    pub limit: u32,
+    /// Requests left in the current window
+    pub remaining: u32,
+    /// Window length in seconds, from the `w=` parameter
+    pub window_secs: Option<u64>,
+    /// Unix time when the headers were seen
+    pub observed_at: u64,
+}
+
+impl RateLimitQuota {
+    /// Parse `ratelimit-limit` and `ratelimit-remaining` values such as `100;w=21600`
+    pub fn parse(limit: &str, remaining: &str, observed_at: u64) -> Option<Self> {
+        let (limit, window_secs) = parse_quota_value(limit)?;
+        let (remaining, remaining_window) = parse_quota_value(remaining)?;
+        Some(Self {
+            limit,
+            remaining,
+            window_secs: window_secs.or(remaining_window),
+            observed_at,
+        })
+    }
+    
+    /// Whether `requests` more requests would use up the remaining quota
+    pub fn would_exhaust(&self, requests: usize) -> bool {
+        requests as u64 >= self.remaining as u64
+    }
+    
+    /// Whether less than a tenth of the quota is left
+    pub fn is_low(&self) -> bool {
+        (self.remaining as u64) * 10 < self.limit as u64
+    }
+    
+    /// Window length for display, e.g. "6h"
+    pub fn window_label(&self) -> Option<String> {
+        self.window_secs.map(|secs| match secs {
+            s if s % 3600 == 0 => format!("{}h", s / 3600),
+            s if s % 60 == 0 => format!("{}m", s / 60),
+            s => format!("{}s", s),
+        })
+    }
+}
+
+/// Split `<count>[;w=<seconds>]` into its count and window
+fn parse_quota_value(value: &str) -> Option<(u32, Option<u64>)> {
+    let mut parts = value.split(';');
+    let count = parts.next()?.trim().parse().ok()?;
+    let window = parts
+        .filter_map(|p| p.trim().strip_prefix("w="))
+        .find_map(|w| w.trim().parse().ok());
+    Some((count, window))
+}
+
 impl RegistryConfig {
     /// Create a new registry configuration with a generated ID
     pub fn new(name: String, url: String, auth: AuthConfig) -> Self {
@@ -125,17 +327,21 @@ impl RegistryConfig {
             name,
             url,
             auth,
+            tls: TlsOptions::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
             status: ConnectionStatus::Unknown,
         }
     }
     
-    /// Prepare for storage by encrypting sensitive data
-    pub fn encrypt_for_storage(&self) -> Result<Self, String> {
+    /// Prepare for storage by encrypting sensitive data, or moving it to the keychain
+    pub fn encrypt_for_storage(&self, backend: CredentialBackend) -> Result<Self, String> {
         Ok(Self {
             id: self.id.clone(),
             name: self.name.clone(),
             url: self.url.clone(),
-            auth: self.auth.encrypt_for_storage()?,
+            auth: self.auth.encrypt_for_storage(backend, &self.id)?,
+            tls: self.tls.clone(),
+            max_retries: self.max_retries,
             status: ConnectionStatus::Unknown,
         })
     }
@@ -147,6 +353,8 @@ impl RegistryConfig {
             name: self.name.clone(),
             url: self.url.clone(),
             auth: self.auth.decrypt_from_storage()?,
+            tls: self.tls.clone(),
+            max_retries: self.max_retries,
             status: ConnectionStatus::Unknown,
         })
     }
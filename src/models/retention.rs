@@ -0,0 +1,187 @@
+//! Tag retention policy models
+
+use serde::{Deserialize, Serialize};
+use super::TagInfo;
+
+/// A single retention rule
+///
+/// Keep rules always win over delete rules when both apply to a tag.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RetentionRule {
+    /// Keep the newest `count` tags matching `pattern`, delete older matching tags
+    KeepNewest { pattern: String, count: usize },
+    /// Delete tags matching `pattern` created more than `days` days ago
+    DeleteOlderThan { pattern: String, days: u32 },
+    /// Always keep semver release tags (e.g. `1.2.3`, `v2.0`)
+    KeepReleases,
+}
+
+impl RetentionRule {
+    /// Human-readable description of the rule
+    pub fn describe(&self) -> String {
+        match self {
+            RetentionRule::KeepNewest { pattern, count } => {
+                format!("Keep the newest {} tags matching '{}'", count, pattern)
+            }
+            RetentionRule::DeleteOlderThan { pattern, days } => {
+                format!("Delete tags matching '{}' older than {} days", pattern, days)
+            }
+            RetentionRule::KeepReleases => "Always keep semver releases".to_string(),
+        }
+    }
+}
+
+/// Set of rules evaluated against the tags of one repository
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    pub rules: Vec<RetentionRule>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                RetentionRule::KeepReleases,
+                RetentionRule::KeepNewest {
+                    pattern: "*".to_string(),
+                    count: 10,
+                },
+            ],
+        }
+    }
+}
+
+/// Outcome of a policy for a single tag
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetentionAction {
+    Keep,
+    Delete,
+}
+
+/// Decision for one tag with the reason behind it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TagDecision {
+    pub tag: TagInfo,
+    pub action: RetentionAction,
+    pub reason: String,
+}
+
+/// Dry-run result of evaluating a policy
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPlan {
+    pub decisions: Vec<TagDecision>,
+}
+
+impl RetentionPlan {
+    /// Tags that would be deleted
+    pub fn to_delete(&self) -> Vec<&TagDecision> {
+        self.decisions
+            .iter()
+            .filter(|d| d.action == RetentionAction::Delete)
+            .collect()
+    }
+    
+    /// Tags that would be kept
+    pub fn to_keep(&self) -> Vec<&TagDecision> {
+        self.decisions
+            .iter()
+            .filter(|d| d.action == RetentionAction::Keep)
+            .collect()
+    }
+    
+    /// Unique manifest digests to delete, in decision order
+    pub fn digests_to_delete(&self) -> Vec<String> {
+        let mut digests: Vec<String> = Vec::new();
+        for decision in self.to_delete() {
+            if !digests.contains(&decision.tag.digest) {
+                digests.push(decision.tag.digest.clone());
+            }
+        }
+        digests
+    }
+    
+    /// Tags whose digest could not be resolved; while any remain nothing is deleted
+    pub fn unresolved(&self) -> Vec<&TagDecision> {
+        self.decisions.iter().filter(|d| d.tag.digest.is_empty()).collect()
+    }
+}
+
+/// Retention policy saved for a registry and run on a schedule
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedRetentionPolicy {
+    /// Unique identifier
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Registry the policy applies to
+    pub registry_id: String,
+    /// Repositories the policy applies to (tag search syntax, e.g. `team/*`)
+    pub repository_pattern: String,
+    /// Rules evaluated per repository
+    pub policy: RetentionPolicy,
+    /// Hours between scheduled runs (0 = manual only)
+    pub interval_hours: u64,
+    /// Whether the scheduler runs the policy
+    pub enabled: bool,
+    /// Unix timestamp of the last run
+    #[serde(default)]
+    pub last_run: Option<u64>,
+}
+
+impl SavedRetentionPolicy {
+    /// Create a new policy for a registry with the default rules
+    pub fn new(name: String, registry_id: String, repository_pattern: String) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            registry_id,
+            repository_pattern,
+            policy: RetentionPolicy::default(),
+            interval_hours: 24 * 7,
+            enabled: false,
+            last_run: None,
+        }
+    }
+    
+    /// Check if the scheduler should run the policy at `now` (Unix seconds)
+    pub fn is_due(&self, now: u64) -> bool {
+        if !self.enabled || self.interval_hours == 0 {
+            return false;
+        }
+        match self.last_run {
+            Some(last) => now.saturating_sub(last) >= self.interval_hours * 3600,
+            None => true,
+        }
+    }
+}
+
+/// Tag deleted (or not) during a retention run
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetentionRunEntry {
+    pub repository: String,
+    pub tag: String,
+    pub digest: String,
+    /// Error message when deletion failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Run log entry for one execution of a saved policy
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetentionRun {
+    pub policy_id: String,
+    pub policy_name: String,
+    pub registry_id: String,
+    /// Unix timestamp when the run started
+    pub started_at: u64,
+    /// Unix timestamp when the run finished
+    pub finished_at: u64,
+    /// Whether the run was started by the scheduler rather than by hand
+    pub scheduled: bool,
+    pub deleted: Vec<RetentionRunEntry>,
+    pub failed: Vec<RetentionRunEntry>,
+    /// Error that aborted the run or a repository within it
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
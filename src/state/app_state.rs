@@ -1,8 +1,10 @@
 //! Application state management
 
+use std::collections::HashMap;
 use dioxus::prelude::*;
-use crate::models::{CacheConfig, RegistryConfig, Theme};
-use crate::storage::get_storage;
+use crate::models::{CacheConfig, DeleteSupport, RegistryConfig, RetentionRun, SavedRetentionPolicy, Theme};
+use crate::storage::{clear_key, delete_secret, get_storage, CredentialBackend};
+use crate::auth::{clear_all_cached_tokens, clear_cached_tokens};
 
 /// Global application state - uses Copy-able Signal wrappers
 #[derive(Clone, Copy)]
@@ -19,6 +21,34 @@ pub struct AppState {
     pub theme: Signal<Theme>,
     /// Cache configuration
     pub cache_config: Signal<CacheConfig>,
+    /// Saved retention policies
+    pub retention_policies: Signal<Vec<SavedRetentionPolicy>>,
+    /// Retention run log, newest first
+    pub retention_log: Signal<Vec<RetentionRun>>,
+    /// IDs of retention policies currently running
+    pub running_policies: Signal<Vec<String>>,
+    /// Probed delete support per registry ID (not persisted)
+    pub delete_support: Signal<HashMap<String, DeleteSupport>>,
+    /// Idle minutes before the app locks itself (0 = never)
+    pub auto_lock_minutes: Signal<u32>,
+    /// Interactive deletions in progress; locking waits for them to finish
+    pub active_deletions: Signal<u32>,
+    /// Where registry secrets are stored; locking needs the master password backend
+    pub credential_backend: Signal<CredentialBackend>,
+}
+
+/// Marks an interactive deletion as running until dropped
+///
+/// Dropped with the task when its dialog unmounts, so an abandoned run cannot
+/// keep the app from locking.
+pub struct DeletionGuard(Signal<u32>);
+
+impl Drop for DeletionGuard {
+    fn drop(&mut self) {
+        if let Ok(mut count) = self.0.try_write() {
+            *count = count.saturating_sub(1);
+        }
+    }
 }
 
 impl AppState {
@@ -31,6 +61,13 @@ impl AppState {
             selected_tag: Signal::new(None),
             theme: Signal::new(Theme::default()),
             cache_config: Signal::new(CacheConfig::default()),
+            retention_policies: Signal::new(Vec::new()),
+            retention_log: Signal::new(Vec::new()),
+            running_policies: Signal::new(Vec::new()),
+            delete_support: Signal::new(HashMap::new()),
+            auto_lock_minutes: Signal::new(crate::storage::DEFAULT_AUTO_LOCK_MINUTES),
+            active_deletions: Signal::new(0),
+            credential_backend: Signal::new(CredentialBackend::default()),
         }
     }
     
@@ -54,15 +91,26 @@ impl AppState {
         {
             let mut registries = self.registries.write();
             if let Some(reg) = registries.iter_mut().find(|r| r.id == id) {
+                // Credentials may have changed, drop tokens issued for the old ones
+                clear_cached_tokens(reg.url.trim_end_matches('/'));
                 *reg = updated;
             }
         }
+        // Server or credentials may have changed, probe again
+        self.delete_support.write().remove(id);
         self.persist_registries();
     }
     
     /// Delete a registry by ID and persist
     pub fn delete_registry(&mut self, id: &str) {
         self.registries.write().retain(|r| r.id != id);
+        if get_storage().credential_backend() == CredentialBackend::Keychain {
+            let _ = delete_secret(id);
+        }
+        if self.retention_policies.peek().iter().any(|p| p.registry_id == id) {
+            self.retention_policies.write().retain(|p| p.registry_id != id);
+            self.persist_retention_policies();
+        }
         if self.selected_registry.read().as_ref() == Some(&id.to_string()) {
             *self.selected_registry.write() = None;
             *self.selected_repo.write() = None;
@@ -76,6 +124,16 @@ impl AppState {
         self.registries.read().iter().find(|r| r.id == id).cloned()
     }
     
+    /// Get the probed delete support of a registry
+    pub fn get_delete_support(&self, id: &str) -> DeleteSupport {
+        self.delete_support.read().get(id).copied().unwrap_or_default()
+    }
+    
+    /// Record the delete support of a registry
+    pub fn set_delete_support(&mut self, id: &str, support: DeleteSupport) {
+        self.delete_support.write().insert(id.to_string(), support);
+    }
+    
     /// Select a registry
     pub fn select_registry(&mut self, id: Option<String>) {
         *self.selected_registry.write() = id;
@@ -107,6 +165,106 @@ impl AppState {
         let storage = get_storage();
         let _ = storage.save_cache_config(&config);
     }
+    
+    /// Set the idle auto-lock timeout and persist
+    pub fn set_auto_lock_minutes(&mut self, minutes: u32) {
+        *self.auto_lock_minutes.write() = minutes;
+        let _ = get_storage().save_auto_lock_minutes(minutes);
+    }
+    
+    /// Register an interactive deletion for as long as the returned guard lives
+    pub fn begin_deletion(&mut self) -> DeletionGuard {
+        *self.active_deletions.write() += 1;
+        DeletionGuard(self.active_deletions)
+    }
+    
+    /// Whether the app can be locked
+    ///
+    /// Keychain-backed credentials are read back without a password, so a lock
+    /// would not protect anything.
+    pub fn can_lock(&self) -> bool {
+        *self.credential_backend.peek() == CredentialBackend::Encrypted
+    }
+    
+    /// Whether a deletion or retention run is in progress, which locking must not interrupt
+    pub fn is_busy(&self) -> bool {
+        *self.active_deletions.peek() > 0 || !self.running_policies.peek().is_empty()
+    }
+    
+    /// Drop decrypted credentials, cached tokens and the encryption key
+    ///
+    /// Secrets are zeroized before they are dropped. The caller is expected to
+    /// unmount the app afterwards, which cancels its in-flight requests.
+    pub fn lock(&mut self) {
+        {
+            let mut registries = self.registries.write();
+            for registry in registries.iter_mut() {
+                registry.auth.zeroize_secrets();
+            }
+            registries.clear();
+        }
+        self.select_registry(None);
+        self.delete_support.write().clear();
+        clear_all_cached_tokens();
+        clear_key();
+    }
+    
+    /// Persist retention policies to storage
+    fn persist_retention_policies(&self) {
+        let policies: Vec<SavedRetentionPolicy> = self.retention_policies.peek().clone();
+        let storage = get_storage();
+        if let Err(e) = storage.save_retention_policies(&policies) {
+            eprintln!("[ERROR] Failed to save retention policies: {:?}", e);
+        }
+    }
+    
+    /// Add a retention policy and persist
+    pub fn add_retention_policy(&mut self, policy: SavedRetentionPolicy) {
+        self.retention_policies.write().push(policy);
+        self.persist_retention_policies();
+    }
+    
+    /// Update an existing retention policy and persist
+    pub fn update_retention_policy(&mut self, updated: SavedRetentionPolicy) {
+        {
+            let mut policies = self.retention_policies.write();
+            if let Some(policy) = policies.iter_mut().find(|p| p.id == updated.id) {
+                *policy = updated;
+            }
+        }
+        self.persist_retention_policies();
+    }
+    
+    /// Delete a retention policy by ID and persist
+    pub fn delete_retention_policy(&mut self, id: &str) {
+        self.retention_policies.write().retain(|p| p.id != id);
+        self.persist_retention_policies();
+    }
+    
+    /// Record a finished retention run: stamp the policy's last run and append to the log
+    pub fn record_retention_run(&mut self, run: RetentionRun) {
+        {
+            let mut policies = self.retention_policies.write();
+            if let Some(policy) = policies.iter_mut().find(|p| p.id == run.policy_id) {
+                policy.last_run = Some(run.started_at);
+            }
+        }
+        self.persist_retention_policies();
+        
+        let storage = get_storage();
+        if let Err(e) = storage.append_retention_run(&run) {
+            eprintln!("[ERROR] Failed to save retention run: {:?}", e);
+        }
+        let mut log = self.retention_log.write();
+        log.insert(0, run);
+        log.truncate(crate::storage::MAX_RETENTION_RUNS);
+    }
+    
+    /// Clear the retention run log
+    pub fn clear_retention_log(&mut self) {
+        self.retention_log.write().clear();
+        let _ = get_storage().clear_retention_log();
+    }
 }
 
 impl Default for AppState {
@@ -1,5 +1,7 @@
 //! Application state management
 
 mod app_state;
+mod scheduler;
 
 pub use app_state::*;
+pub use scheduler::*;
@@ -0,0 +1,193 @@
+//! Background runner for saved retention policies
+
+use std::time::Duration;
+use dioxus::prelude::*;
+use crate::api::{ApiError, RegistryClient, TAG_INFO_CONCURRENCY};
+use crate::models::{RegistryConfig, RetentionRun, RetentionRunEntry, SavedRetentionPolicy, TagInfo};
+use crate::utils::{evaluate_retention, sleep, unix_now, NameFilter};
+use super::AppState;
+
+/// How often the scheduler checks for due policies
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+/// Run due retention policies in the background while the app is open
+///
+/// Must be called from a component that stays mounted for the whole session.
+pub fn use_retention_scheduler(app_state: AppState) {
+    use_future(move || async move {
+        loop {
+            let now = unix_now();
+            let due: Vec<SavedRetentionPolicy> = app_state
+                .retention_policies
+                .peek()
+                .iter()
+                .filter(|p| p.is_due(now))
+                .cloned()
+                .collect();
+            
+            for policy in due {
+                execute_retention_policy(app_state, policy, true).await;
+            }
+            
+            sleep(SCHEDULER_TICK).await;
+        }
+    });
+}
+
+/// Run a saved policy and record the result in the run log
+///
+/// Does nothing when the policy is already running.
+pub async fn execute_retention_policy(mut app_state: AppState, policy: SavedRetentionPolicy, scheduled: bool) {
+    if app_state.running_policies.peek().contains(&policy.id) {
+        return;
+    }
+    app_state.running_policies.write().push(policy.id.clone());
+    
+    let page_size = app_state.cache_config.peek().page_size;
+    let support = app_state.get_delete_support(&policy.registry_id);
+    let run = match (app_state.get_registry(&policy.registry_id), support.explanation()) {
+        (Some(registry), None) => run_retention_policy(&registry, &policy, page_size, scheduled).await,
+        (registry, reason) => {
+            let now = unix_now();
+            let error = match registry {
+                Some(_) => reason.unwrap_or_default().to_string(),
+                None => "Registry no longer exists".to_string(),
+            };
+            RetentionRun {
+                policy_id: policy.id.clone(),
+                policy_name: policy.name.clone(),
+                registry_id: policy.registry_id.clone(),
+                started_at: now,
+                finished_at: now,
+                scheduled,
+                deleted: Vec::new(),
+                failed: Vec::new(),
+                errors: vec![error],
+            }
+        }
+    };
+    
+    app_state.record_retention_run(run);
+    app_state.running_policies.write().retain(|id| id != &policy.id);
+}
+
+/// Apply a saved policy to every matching repository of a registry
+///
+/// Each repository is evaluated on its own with `evaluate_retention`, so the same
+/// safety rules as the interactive dialog apply. Errors in one repository are
+/// recorded and do not stop the others.
+pub async fn run_retention_policy(
+    registry: &RegistryConfig,
+    policy: &SavedRetentionPolicy,
+    page_size: usize,
+    scheduled: bool,
+) -> RetentionRun {
+    let mut run = RetentionRun {
+        policy_id: policy.id.clone(),
+        policy_name: policy.name.clone(),
+        registry_id: registry.id.clone(),
+        started_at: unix_now(),
+        finished_at: 0,
+        scheduled,
+        deleted: Vec::new(),
+        failed: Vec::new(),
+        errors: Vec::new(),
+    };
+    
+    if let Err(e) = apply_to_registry(registry, policy, page_size, &mut run).await {
+        run.errors.push(e);
+    }
+    
+    run.finished_at = unix_now();
+    run
+}
+
+async fn apply_to_registry(
+    registry: &RegistryConfig,
+    policy: &SavedRetentionPolicy,
+    page_size: usize,
+    run: &mut RetentionRun,
+) -> Result<(), String> {
+    let filter = NameFilter::parse(&policy.repository_pattern)?;
+    let client = RegistryClient::for_registry(registry)
+        .map_err(|e| format!("Client error: {}", e))?;
+    
+    let repositories = client
+        .list_all_repositories(page_size)
+        .await
+        .map_err(|e| format!("Failed to list repositories: {}", e))?;
+    
+    for repo in repositories.iter().filter(|r| filter.matches(r)) {
+        let names = match client.list_all_tags(repo, page_size).await {
+            Ok(names) => names,
+            Err(e) => {
+                run.errors.push(format!("{}: failed to list tags: {}", repo, e));
+                continue;
+            }
+        };
+        
+        // Unattended runs never spend the last of a shared pull quota
+        if let Some(first) = names.first() {
+            if let Some(quota) = client.quota_shortfall(repo, first, names.len()).await {
+                run.errors.push(format!(
+                    "{}: skipped, resolving {} tags would exhaust the pull quota ({} of {} left)",
+                    repo,
+                    names.len(),
+                    quota.remaining,
+                    quota.limit
+                ));
+                continue;
+            }
+        }
+        
+        let mut tags: Vec<TagInfo> = names.iter().cloned().map(TagInfo::new).collect();
+        let mut unresolved: Vec<String> = Vec::new();
+        client
+            .get_tag_infos_with(repo, names, TAG_INFO_CONCURRENCY, |name, result| match result {
+                Ok(info) => {
+                    if let Some(row) = tags.iter_mut().find(|t| t.name == info.name) {
+                        *row = info;
+                    }
+                }
+                Err(_) => unresolved.push(name),
+            })
+            .await;
+        
+        // An unresolved tag may share a manifest with any tag marked for deletion
+        if !unresolved.is_empty() {
+            run.errors.push(format!(
+                "{}: skipped, could not resolve {}",
+                repo,
+                unresolved.join(", ")
+            ));
+            continue;
+        }
+        
+        // Rule patterns are the same for every repository, so an invalid one aborts the run
+        let plan = evaluate_retention(&policy.policy, &tags, chrono::Utc::now())?;
+        
+        for digest in plan.digests_to_delete() {
+            let result = client.delete_manifest(repo, &digest).await;
+            let disabled = matches!(&result, Err(e) if matches!(e.kind(), ApiError::MethodNotAllowed(_)));
+            let error = result.err().map(|e| e.to_string());
+            for decision in plan.to_delete().into_iter().filter(|d| d.tag.digest == digest) {
+                let entry = RetentionRunEntry {
+                    repository: repo.clone(),
+                    tag: decision.tag.name.clone(),
+                    digest: digest.clone(),
+                    error: error.clone(),
+                };
+                if entry.error.is_some() {
+                    run.failed.push(entry);
+                } else {
+                    run.deleted.push(entry);
+                }
+            }
+            if disabled {
+                return Err("Deletion is disabled on this registry, stopping the run".to_string());
+            }
+        }
+    }
+    
+    Ok(())
+}
@@ -34,6 +34,15 @@ impl DesktopStorage {
         Ok(Self { base_path })
     }
     
+    /// Create a storage adapter rooted at `base_path` instead of the data directory
+    #[cfg(test)]
+    pub(crate) fn at(base_path: PathBuf) -> Result<Self, StorageError> {
+        fs::create_dir_all(&base_path)
+            .map_err(|e| StorageError::IoError(e.to_string()))?;
+        
+        Ok(Self { base_path })
+    }
+    
     fn key_to_path(&self, key: &str) -> PathBuf {
         let safe_key = key.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_");
         self.base_path.join(format!("{}.dat", safe_key))
@@ -48,8 +57,14 @@ impl Default for DesktopStorage {
 
 impl StorageAdapter for DesktopStorage {
     fn store(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        // Write next to the target and rename over it, so a crash leaves the old data intact
         let path = self.key_to_path(key);
-        fs::write(&path, data).map_err(|e| StorageError::IoError(e.to_string()))
+        let staging = path.with_extension("tmp");
+        if let Err(e) = fs::write(&staging, data) {
+            let _ = fs::remove_file(&staging);
+            return Err(StorageError::IoError(e.to_string()));
+        }
+        fs::rename(&staging, &path).map_err(|e| StorageError::IoError(e.to_string()))
     }
     
     fn retrieve(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
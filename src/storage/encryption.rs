@@ -1,36 +1,165 @@
 //! AES-GCM encryption for credential storage
+//!
+//! The key is derived from the master password with Argon2id. Ciphertext is
+//! stored as `v2:<base64(nonce || ciphertext)>`; bare base64 without a version
+//! header is the legacy format keyed with an unsalted SHA-256 of the password,
+//! which is still readable so existing configs can be migrated on unlock.
 
 use super::StorageError;
 use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Nonce,
 };
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use rand::Rng;
-use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::RwLock;
+use zeroize::{Zeroize, Zeroizing};
 
-// Key for AES-256 (initialized at runtime)
-static ENCRYPTION_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+/// Version header of ciphertext encrypted with the KDF-derived key
+const CURRENT_FORMAT: &str = "v2:";
 
-/// Initialize the encryption key
-pub fn init_key(key: [u8; 32]) -> Result<(), StorageError> {
-    ENCRYPTION_KEY.set(key)
-        .map_err(|_| StorageError::EncryptionError("Encryption key already initialized".to_string()))
+/// Only supported key derivation algorithm
+pub const KDF_ARGON2ID: &str = "argon2id";
+
+/// Default Argon2id memory cost in KiB (19 MiB)
+const DEFAULT_MEMORY_KIB: u32 = 19 * 1024;
+
+/// Default Argon2id passes over memory
+const DEFAULT_ITERATIONS: u32 = 2;
+
+/// Default Argon2id lanes
+const DEFAULT_PARALLELISM: u32 = 1;
+
+/// Salt length in bytes
+const SALT_LEN: usize = 16;
+
+// Key for AES-256 (set at runtime, replaced when the master password changes)
+static ENCRYPTION_KEY: RwLock<Option<[u8; 32]>> = RwLock::new(None);
+
+// Legacy SHA-256 key, only used to read unversioned ciphertext
+static LEGACY_KEY: RwLock<Option<[u8; 32]>> = RwLock::new(None);
+
+/// Serializes tests that replace the process-wide keys
+#[cfg(test)]
+pub(crate) static KEY_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Key derivation settings, stored with the config so the key can be re-derived
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Algorithm name, currently always `argon2id`
+    pub algorithm: String,
+    /// Base64 encoded random salt
+    pub salt: String,
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes
+    pub iterations: u32,
+    /// Degree of parallelism
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// Default cost parameters with a fresh random salt
+    pub fn generate() -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill(&mut salt);
+        Self {
+            algorithm: KDF_ARGON2ID.to_string(),
+            salt: STANDARD.encode(salt),
+            memory_kib: DEFAULT_MEMORY_KIB,
+            iterations: DEFAULT_ITERATIONS,
+            parallelism: DEFAULT_PARALLELISM,
+        }
+    }
+    
+    /// Derive the 32-byte AES key from `password`
+    pub fn derive_key(&self, password: &str) -> Result<Zeroizing<[u8; 32]>, StorageError> {
+        if self.algorithm != KDF_ARGON2ID {
+            return Err(StorageError::EncryptionError(format!(
+                "Unsupported key derivation algorithm: {}",
+                self.algorithm
+            )));
+        }
+        
+        let salt = STANDARD
+            .decode(&self.salt)
+            .map_err(|e| StorageError::EncryptionError(e.to_string()))?;
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| StorageError::EncryptionError(e.to_string()))?;
+        
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), &salt, key.as_mut_slice())
+            .map_err(|e| StorageError::EncryptionError(e.to_string()))?;
+        Ok(key)
+    }
+}
+
+/// Key used by the legacy format: a single unsalted SHA-256 of the password
+pub fn legacy_key(password: &str) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&Sha256::digest(password.as_bytes()));
+    key
+}
+
+/// Set the encryption key, replacing any previous one
+pub fn set_key(key: Zeroizing<[u8; 32]>) {
+    if let Ok(mut current) = ENCRYPTION_KEY.write() {
+        *current = Some(*key);
+    }
+}
+
+/// Set the key used to read legacy, unversioned ciphertext
+pub fn set_legacy_key(key: Zeroizing<[u8; 32]>) {
+    if let Ok(mut current) = LEGACY_KEY.write() {
+        *current = Some(*key);
+    }
 }
 
-fn get_key() -> Result<&'static [u8; 32], StorageError> {
-    ENCRYPTION_KEY.get()
+/// Zeroize and drop the encryption keys, locking stored credentials until the next unlock
+pub fn clear_key() {
+    for slot in [&ENCRYPTION_KEY, &LEGACY_KEY] {
+        if let Ok(mut key) = slot.write() {
+            if let Some(key) = key.as_mut() {
+                key.zeroize();
+            }
+            *key = None;
+        }
+    }
+}
+
+/// Whether an encryption key has been set
+pub fn is_key_initialized() -> bool {
+    current_key().is_ok()
+}
+
+/// Copy of the encryption key in use, zeroized when dropped
+pub fn current_key() -> Result<Zeroizing<[u8; 32]>, StorageError> {
+    ENCRYPTION_KEY.read().ok().and_then(|key| key.map(Zeroizing::new))
         .ok_or_else(|| StorageError::EncryptionError("Encryption key not initialized".to_string()))
 }
 
-/// Encrypt a string using AES-256-GCM and return base64 encoded result
+/// Whether `data` was encrypted in the legacy format and should be re-encrypted
+pub fn is_legacy_ciphertext(data: &str) -> bool {
+    !data.is_empty() && !data.starts_with(CURRENT_FORMAT)
+}
+
+fn get_legacy_key() -> Result<Zeroizing<[u8; 32]>, StorageError> {
+    LEGACY_KEY.read().ok().and_then(|key| key.map(Zeroizing::new))
+        .ok_or_else(|| StorageError::EncryptionError("Legacy encryption key not initialized".to_string()))
+}
+
+/// Encrypt a string using AES-256-GCM and return the versioned, base64 encoded result
 pub fn encrypt_string(data: &str) -> Result<String, StorageError> {
     if data.is_empty() {
         return Ok(String::new());
     }
     
-    let key = get_key()?;
-    let cipher = Aes256Gcm::new_from_slice(key)
+    let key = current_key()?;
+    let cipher = Aes256Gcm::new_from_slice(key.as_slice())
         .map_err(|e| StorageError::EncryptionError(e.to_string()))?;
     
     // Generate random 12-byte nonce
@@ -46,25 +175,29 @@ pub fn encrypt_string(data: &str) -> Result<String, StorageError> {
     let mut result = nonce_bytes.to_vec();
     result.extend(ciphertext);
     
-    Ok(STANDARD.encode(&result))
+    Ok(format!("{}{}", CURRENT_FORMAT, STANDARD.encode(&result)))
 }
 
-/// Decrypt a base64 encoded AES-256-GCM encrypted string
+/// Decrypt a string produced by `encrypt_string`, or by the legacy format
 pub fn decrypt_string(data: &str) -> Result<String, StorageError> {
     if data.is_empty() {
         return Ok(String::new());
     }
     
+    let (encoded, key) = match data.strip_prefix(CURRENT_FORMAT) {
+        Some(encoded) => (encoded, current_key()?),
+        None => (data, get_legacy_key()?),
+    };
+    
     let decoded = STANDARD
-        .decode(data)
+        .decode(encoded)
         .map_err(|e| StorageError::EncryptionError(e.to_string()))?;
     
     if decoded.len() < 12 {
         return Err(StorageError::EncryptionError("Invalid encrypted data".to_string()));
     }
     
-    let key = get_key()?;
-    let cipher = Aes256Gcm::new_from_slice(key)
+    let cipher = Aes256Gcm::new_from_slice(key.as_slice())
         .map_err(|e| StorageError::EncryptionError(e.to_string()))?;
     
     // Extract nonce and ciphertext
@@ -78,3 +211,56 @@ pub fn decrypt_string(data: &str) -> Result<String, StorageError> {
     String::from_utf8(plaintext)
         .map_err(|e| StorageError::EncryptionError(e.to_string()))
 }
+
+/// Encrypt `data` in the legacy format, replacing the current key
+#[cfg(test)]
+pub(crate) fn encrypt_legacy(data: &str, password: &str) -> String {
+    // Same cipher and layout, only without the version header
+    set_key(legacy_key(password));
+    let encrypted = encrypt_string(data).unwrap();
+    encrypted[CURRENT_FORMAT.len()..].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn legacy_ciphertext_is_read_and_reencrypted_as_v2() {
+        let _guard = KEY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let legacy = encrypt_legacy("secret", "password");
+        assert!(is_legacy_ciphertext(&legacy));
+        
+        set_key(KdfParams::generate().derive_key("password").unwrap());
+        set_legacy_key(legacy_key("password"));
+        assert_eq!(decrypt_string(&legacy).unwrap(), "secret");
+        
+        let migrated = encrypt_string("secret").unwrap();
+        assert!(migrated.starts_with(CURRENT_FORMAT));
+        assert!(!is_legacy_ciphertext(&migrated));
+        assert_eq!(decrypt_string(&migrated).unwrap(), "secret");
+    }
+    
+    #[test]
+    fn wrong_password_is_rejected_and_can_be_retried() {
+        let _guard = KEY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let params = KdfParams::generate();
+        set_key(params.derive_key("password").unwrap());
+        let encrypted = encrypt_string("secret").unwrap();
+        
+        set_key(params.derive_key("mistyped").unwrap());
+        assert!(decrypt_string(&encrypted).is_err());
+        
+        set_key(params.derive_key("password").unwrap());
+        assert_eq!(decrypt_string(&encrypted).unwrap(), "secret");
+    }
+    
+    #[test]
+    fn derived_key_depends_on_salt() {
+        let first = KdfParams::generate();
+        let second = KdfParams::generate();
+        assert_ne!(first.salt, second.salt);
+        assert_ne!(first.derive_key("password").unwrap(), second.derive_key("password").unwrap());
+        assert_eq!(first.derive_key("password").unwrap(), first.derive_key("password").unwrap());
+    }
+}
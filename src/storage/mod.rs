@@ -6,6 +6,7 @@ mod desktop;
 #[cfg(target_arch = "wasm32")]
 mod web;
 pub mod encryption;
+pub mod secrets;
 mod service;
 
 pub use adapter::*;
@@ -13,5 +14,6 @@ pub use adapter::*;
 pub use desktop::*;
 #[cfg(target_arch = "wasm32")]
 pub use web::*;
-pub use encryption::{decrypt_string, encrypt_string, init_key};
+pub use encryption::{clear_key, decrypt_string, encrypt_string, is_key_initialized, set_key, KdfParams};
+pub use secrets::{delete_secret, CredentialBackend};
 pub use service::*;
@@ -0,0 +1,190 @@
+//! Credential storage in the OS keychain instead of the encrypted config
+//!
+//! With the keychain backend each registry's password or token lives in the
+//! platform secret store (Secret Service on Linux, Keychain on macOS, Credential
+//! Manager on Windows) and the stored config only keeps a `keychain:<entry>`
+//! reference. Builds with the `insecure-file-secrets` feature can swap the keychain
+//! for a plaintext JSON file named by `REGISTRY_MANAGER_SECRETS_FILE`, meant for
+//! tests and headless machines; release builds never read it.
+
+use super::StorageError;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+#[cfg(feature = "insecure-file-secrets")]
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
+
+/// Prefix of a secret field that references a keychain entry
+const REFERENCE_PREFIX: &str = "keychain:";
+
+/// Service name entries are stored under
+const KEYCHAIN_SERVICE: &str = "docker-registry-manager";
+
+/// Environment variable selecting the file-based secret store
+#[cfg(feature = "insecure-file-secrets")]
+pub const SECRETS_FILE_ENV: &str = "REGISTRY_MANAGER_SECRETS_FILE";
+
+/// Where registry passwords and tokens are kept
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CredentialBackend {
+    /// Encrypted into the config with the master password key
+    #[default]
+    Encrypted,
+    /// OS keychain / Secret Service, no master password needed
+    Keychain,
+}
+
+/// Platform secret store holding one secret per entry
+pub trait SecretStore: Send + Sync {
+    /// Store or replace the secret of an entry
+    fn set(&self, entry: &str, secret: &str) -> Result<(), StorageError>;
+    
+    /// Read the secret of an entry
+    fn get(&self, entry: &str) -> Result<Option<String>, StorageError>;
+    
+    /// Remove an entry, succeeding if it does not exist
+    fn delete(&self, entry: &str) -> Result<(), StorageError>;
+}
+
+/// Secret store backed by the OS keychain
+#[cfg(not(target_arch = "wasm32"))]
+pub struct KeychainStore;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KeychainStore {
+    fn entry(entry: &str) -> Result<keyring::Entry, StorageError> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, entry)
+            .map_err(|e| StorageError::IoError(format!("Keychain unavailable: {}", e)))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SecretStore for KeychainStore {
+    fn set(&self, entry: &str, secret: &str) -> Result<(), StorageError> {
+        Self::entry(entry)?
+            .set_password(secret)
+            .map_err(|e| StorageError::IoError(format!("Keychain write failed: {}", e)))
+    }
+    
+    fn get(&self, entry: &str) -> Result<Option<String>, StorageError> {
+        match Self::entry(entry)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(StorageError::IoError(format!("Keychain read failed: {}", e))),
+        }
+    }
+    
+    fn delete(&self, entry: &str) -> Result<(), StorageError> {
+        match Self::entry(entry)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(StorageError::IoError(format!("Keychain delete failed: {}", e))),
+        }
+    }
+}
+
+/// Secret store keeping entries in a plaintext JSON file
+#[cfg(feature = "insecure-file-secrets")]
+pub struct FileSecretStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+#[cfg(feature = "insecure-file-secrets")]
+impl FileSecretStore {
+    /// Create a store reading and writing `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, lock: Mutex::new(()) }
+    }
+    
+    fn read(&self) -> Result<HashMap<String, String>, StorageError> {
+        match std::fs::read(&self.path) {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|e| StorageError::SerializationError(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(StorageError::IoError(e.to_string())),
+        }
+    }
+    
+    fn write(&self, secrets: &HashMap<String, String>) -> Result<(), StorageError> {
+        let json = serde_json::to_vec(secrets)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        std::fs::write(&self.path, json).map_err(|e| StorageError::IoError(e.to_string()))
+    }
+}
+
+#[cfg(feature = "insecure-file-secrets")]
+impl SecretStore for FileSecretStore {
+    fn set(&self, entry: &str, secret: &str) -> Result<(), StorageError> {
+        let _guard = self.lock.lock().map_err(|_| StorageError::NotAvailable)?;
+        let mut secrets = self.read()?;
+        secrets.insert(entry.to_string(), secret.to_string());
+        self.write(&secrets)
+    }
+    
+    fn get(&self, entry: &str) -> Result<Option<String>, StorageError> {
+        let _guard = self.lock.lock().map_err(|_| StorageError::NotAvailable)?;
+        Ok(self.read()?.remove(entry))
+    }
+    
+    fn delete(&self, entry: &str) -> Result<(), StorageError> {
+        let _guard = self.lock.lock().map_err(|_| StorageError::NotAvailable)?;
+        let mut secrets = self.read()?;
+        if secrets.remove(entry).is_some() {
+            self.write(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+/// The secret store in use: the OS keychain, or with the `insecure-file-secrets`
+/// feature the file named by `SECRETS_FILE_ENV` when set
+pub fn secret_store() -> Result<&'static dyn SecretStore, StorageError> {
+    static STORE: OnceLock<Option<Box<dyn SecretStore>>> = OnceLock::new();
+    STORE
+        .get_or_init(|| {
+            #[cfg(feature = "insecure-file-secrets")]
+            if let Some(path) = std::env::var_os(SECRETS_FILE_ENV) {
+                return Some(Box::new(FileSecretStore::new(PathBuf::from(path))) as Box<dyn SecretStore>);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                Some(Box::new(KeychainStore) as Box<dyn SecretStore>)
+            }
+            #[cfg(target_arch = "wasm32")]
+            {
+                None
+            }
+        })
+        .as_deref()
+        .ok_or(StorageError::NotAvailable)
+}
+
+/// Fail early if the secret store cannot be reached, e.g. no Secret Service is running
+pub fn check_available() -> Result<(), StorageError> {
+    secret_store()?.get(KEYCHAIN_SERVICE).map(|_| ())
+}
+
+/// Store `secret` under `entry` and return the reference kept in the config
+pub fn store_secret(entry: &str, secret: &str) -> Result<String, StorageError> {
+    secret_store()?.set(entry, secret)?;
+    Ok(format!("{}{}", REFERENCE_PREFIX, entry))
+}
+
+/// Read the secret a config reference points to
+pub fn load_secret(reference: &str) -> Result<String, StorageError> {
+    let entry = reference
+        .strip_prefix(REFERENCE_PREFIX)
+        .ok_or_else(|| StorageError::NotFound(reference.to_string()))?;
+    secret_store()?
+        .get(entry)?
+        .ok_or_else(|| StorageError::NotFound(format!("Keychain entry {}", entry)))
+}
+
+/// Remove an entry from the secret store
+pub fn delete_secret(entry: &str) -> Result<(), StorageError> {
+    secret_store()?.delete(entry)
+}
+
+/// Whether a stored secret field is a keychain reference rather than ciphertext
+pub fn is_secret_reference(data: &str) -> bool {
+    data.starts_with(REFERENCE_PREFIX)
+}
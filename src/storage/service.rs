@@ -1,7 +1,10 @@
 //! Storage service for persisting application data
 
-use crate::models::{CacheConfig, RegistryConfig, Theme};
+use crate::models::{CacheConfig, RegistryConfig, RetentionRun, SavedRetentionPolicy, Theme};
+use super::encryption::{current_key, legacy_key, set_key, set_legacy_key, KdfParams};
+use super::secrets::{check_available, delete_secret, CredentialBackend};
 use super::{StorageAdapter, StorageError};
+use serde::{Deserialize, Serialize};
 
 #[cfg(not(target_arch = "wasm32"))]
 use super::DesktopStorage;
@@ -9,6 +12,42 @@ use super::DesktopStorage;
 const REGISTRIES_KEY: &str = "registries";
 const THEME_KEY: &str = "theme";
 const CACHE_CONFIG_KEY: &str = "cache_config";
+const RETENTION_POLICIES_KEY: &str = "retention_policies";
+const RETENTION_LOG_KEY: &str = "retention_log";
+const KDF_PARAMS_KEY: &str = "kdf_params";
+const AUTO_LOCK_KEY: &str = "auto_lock_minutes";
+const CREDENTIAL_BACKEND_KEY: &str = "credential_backend";
+
+/// Idle minutes before the app locks itself when nothing is configured
+///
+/// Off by default: a locked app drops its credentials, so scheduled retention
+/// policies cannot run until it is unlocked again.
+pub const DEFAULT_AUTO_LOCK_MINUTES: u32 = 0;
+
+/// Maximum number of retention runs kept in the run log
+pub const MAX_RETENTION_RUNS: usize = 100;
+
+/// Registries file: the config together with everything needed to decrypt it
+///
+/// Keeping the KDF parameters and backend in the same file means a single write
+/// replaces all of them, so a crash cannot pair ciphertext with the wrong key.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistriesEnvelope {
+    #[serde(default)]
+    backend: CredentialBackend,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<KdfParams>,
+    registries: Vec<RegistryConfig>,
+}
+
+/// Registries file as written by any version
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredRegistries {
+    /// Plain list, with KDF parameters and backend in their own files
+    Legacy(Vec<RegistryConfig>),
+    Envelope(RegistriesEnvelope),
+}
 
 /// Storage service for application data
 pub struct StorageService {
@@ -27,40 +66,164 @@ impl StorageService {
     
     /// Save registries to storage (with encryption for sensitive data)
     pub fn save_registries(&self, registries: &[RegistryConfig]) -> Result<(), StorageError> {
+        let envelope = self.load_envelope()?.unwrap_or_default();
+        self.store_envelope(envelope.backend, envelope.kdf, registries)
+    }
+    
+    /// Encrypt registries for `backend` and write them with their KDF parameters in one file
+    fn store_envelope(
+        &self,
+        backend: CredentialBackend,
+        kdf: Option<KdfParams>,
+        registries: &[RegistryConfig],
+    ) -> Result<(), StorageError> {
         // Encrypt sensitive data before saving
         let encrypted_registries: Result<Vec<RegistryConfig>, String> = registries
             .iter()
-            .map(|r| r.encrypt_for_storage())
+            .map(|r| r.encrypt_for_storage(backend))
             .collect();
         
-        let encrypted_registries = encrypted_registries
-            .map_err(|e| StorageError::EncryptionError(e))?;
+        let envelope = RegistriesEnvelope {
+            backend,
+            kdf,
+            registries: encrypted_registries.map_err(StorageError::EncryptionError)?,
+        };
+        let json = serde_json::to_string(&envelope)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        self.adapter.store(REGISTRIES_KEY, json.as_bytes())?;
         
-        let json = serde_json::to_string(&encrypted_registries)
+        // Superseded by the envelope
+        let _ = self.adapter.remove(KDF_PARAMS_KEY);
+        let _ = self.adapter.remove(CREDENTIAL_BACKEND_KEY);
+        Ok(())
+    }
+    
+    /// Read the registries file, filling in the separate files of the legacy format
+    fn load_envelope(&self) -> Result<Option<RegistriesEnvelope>, StorageError> {
+        let Some(data) = self.adapter.retrieve(REGISTRIES_KEY)? else {
+            return Ok(None);
+        };
+        let stored: StoredRegistries = serde_json::from_slice(&data)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-        self.adapter.store(REGISTRIES_KEY, json.as_bytes())
+        
+        Ok(Some(match stored {
+            StoredRegistries::Envelope(envelope) => envelope,
+            StoredRegistries::Legacy(registries) => RegistriesEnvelope {
+                backend: self.load_legacy(CREDENTIAL_BACKEND_KEY)?.unwrap_or_default(),
+                kdf: self.load_legacy(KDF_PARAMS_KEY)?,
+                registries,
+            },
+        }))
+    }
+    
+    /// Read a value the legacy format kept in its own file
+    fn load_legacy<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<Option<T>, StorageError> {
+        match self.adapter.retrieve(key)? {
+            Some(data) => serde_json::from_slice(&data)
+                .map(Some)
+                .map_err(|e| StorageError::SerializationError(e.to_string())),
+            None => Ok(None),
+        }
+    }
+    
+    /// Where registry secrets are stored, the encrypted config unless the keychain was chosen
+    pub fn credential_backend(&self) -> CredentialBackend {
+        match self.load_envelope() {
+            Ok(Some(envelope)) => envelope.backend,
+            _ => CredentialBackend::default(),
+        }
+    }
+    
+    /// Move every registry secret into the OS keychain; no master password is needed afterwards
+    pub fn use_keychain(&self, registries: &[RegistryConfig]) -> Result<(), StorageError> {
+        check_available()?;
+        // Nothing is encrypted with the master password any more
+        self.store_envelope(CredentialBackend::Keychain, None, registries)
+    }
+    
+    /// Encrypt every registry secret with a key derived from `password` and remove them from the keychain
+    pub fn use_master_password(&self, registries: &[RegistryConfig], password: &str) -> Result<(), StorageError> {
+        let params = KdfParams::generate();
+        set_key(params.derive_key(password)?);
+        self.store_envelope(CredentialBackend::Encrypted, Some(params), registries)?;
+        
+        for registry in registries {
+            let _ = delete_secret(&registry.id);
+        }
+        Ok(())
     }
     
     /// Load registries from storage (with decryption for sensitive data)
     pub fn load_registries(&self) -> Result<Vec<RegistryConfig>, StorageError> {
-        match self.adapter.retrieve(REGISTRIES_KEY)? {
-            Some(data) => {
-                let json = String::from_utf8(data)
-                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-                
-                let registries: Vec<RegistryConfig> = serde_json::from_str(&json)
-                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-                
-                // Decrypt sensitive data after loading
-                let decrypted_registries: Result<Vec<RegistryConfig>, String> = registries
-                    .iter()
-                    .map(|r| r.decrypt_from_storage())
-                    .collect();
-                
-                decrypted_registries.map_err(|e| StorageError::EncryptionError(e))
-            }
-            None => Ok(Vec::new()),
+        let registries = self.load_encrypted_registries()?;
+        
+        // Decrypt sensitive data after loading
+        let decrypted_registries: Result<Vec<RegistryConfig>, String> = registries
+            .iter()
+            .map(|r| r.decrypt_from_storage())
+            .collect();
+        
+        decrypted_registries.map_err(StorageError::EncryptionError)
+    }
+    
+    /// Load registries as stored, with secrets still encrypted
+    fn load_encrypted_registries(&self) -> Result<Vec<RegistryConfig>, StorageError> {
+        Ok(self.load_envelope()?.map(|e| e.registries).unwrap_or_default())
+    }
+    
+    /// Load the key derivation parameters, `None` for configs from before the KDF
+    pub fn load_kdf_params(&self) -> Result<Option<KdfParams>, StorageError> {
+        Ok(self.load_envelope()?.and_then(|e| e.kdf))
+    }
+    
+    /// Derive the encryption key from the master password and verify it against the stored config
+    ///
+    /// On first run new KDF parameters are generated. Configs still encrypted in the
+    /// legacy SHA-256 format are re-encrypted with the derived key once the password
+    /// has been verified.
+    pub fn unlock(&self, password: &str) -> Result<(), StorageError> {
+        let stored_params = if self.has_config() { self.load_kdf_params()? } else { None };
+        let params = stored_params.clone().unwrap_or_else(KdfParams::generate);
+        set_key(params.derive_key(password)?);
+        set_legacy_key(legacy_key(password));
+        
+        if !self.has_config() {
+            return self.store_envelope(CredentialBackend::Encrypted, Some(params), &[]);
+        }
+        
+        // Decrypting verifies the password
+        let registries = self.load_registries()?;
+        
+        let needs_migration = stored_params.is_none()
+            || self.load_encrypted_registries()?.iter().any(|r| r.auth.has_legacy_secrets());
+        if needs_migration {
+            self.store_envelope(CredentialBackend::Encrypted, Some(params), &registries)?;
+        }
+        Ok(())
+    }
+    
+    /// Verify `old_password`, then re-encrypt every stored credential under a key
+    /// derived from `new_password` with fresh KDF parameters
+    ///
+    /// Ciphertext and parameters are replaced by a single write, so if it fails the
+    /// stored config is still the old one and the previous key is restored with it.
+    pub fn change_password(&self, old_password: &str, new_password: &str) -> Result<(), StorageError> {
+        let params = self.load_kdf_params()?
+            .ok_or_else(|| StorageError::EncryptionError("No key derivation parameters stored".to_string()))?;
+        let old_key = current_key()?;
+        if params.derive_key(old_password)? != old_key {
+            return Err(StorageError::EncryptionError("Current password is incorrect".to_string()));
+        }
+        
+        let registries = self.load_registries()?;
+        let new_params = KdfParams::generate();
+        set_key(new_params.derive_key(new_password)?);
+        
+        let result = self.store_envelope(CredentialBackend::Encrypted, Some(new_params), &registries);
+        if result.is_err() {
+            set_key(old_key);
         }
+        result
     }
     
     /// Save theme to storage
@@ -83,6 +246,26 @@ impl StorageService {
         }
     }
     
+    /// Save the idle auto-lock timeout (0 = disabled)
+    pub fn save_auto_lock_minutes(&self, minutes: u32) -> Result<(), StorageError> {
+        let json = serde_json::to_string(&minutes)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        self.adapter.store(AUTO_LOCK_KEY, json.as_bytes())
+    }
+    
+    /// Load the idle auto-lock timeout
+    pub fn load_auto_lock_minutes(&self) -> Result<u32, StorageError> {
+        match self.adapter.retrieve(AUTO_LOCK_KEY)? {
+            Some(data) => {
+                let json = String::from_utf8(data)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+                serde_json::from_str(&json)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))
+            }
+            None => Ok(DEFAULT_AUTO_LOCK_MINUTES),
+        }
+    }
+    
     /// Save cache config to storage
     pub fn save_cache_config(&self, config: &CacheConfig) -> Result<(), StorageError> {
         let json = serde_json::to_string(config)
@@ -103,6 +286,55 @@ impl StorageService {
         }
     }
     
+    /// Save retention policies to storage
+    pub fn save_retention_policies(&self, policies: &[SavedRetentionPolicy]) -> Result<(), StorageError> {
+        let json = serde_json::to_string(policies)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        self.adapter.store(RETENTION_POLICIES_KEY, json.as_bytes())
+    }
+    
+    /// Load retention policies from storage
+    pub fn load_retention_policies(&self) -> Result<Vec<SavedRetentionPolicy>, StorageError> {
+        match self.adapter.retrieve(RETENTION_POLICIES_KEY)? {
+            Some(data) => {
+                let json = String::from_utf8(data)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+                serde_json::from_str(&json)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+    
+    /// Load the retention run log, newest first
+    pub fn load_retention_log(&self) -> Result<Vec<RetentionRun>, StorageError> {
+        match self.adapter.retrieve(RETENTION_LOG_KEY)? {
+            Some(data) => {
+                let json = String::from_utf8(data)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+                serde_json::from_str(&json)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+    
+    /// Prepend a run to the retention run log, dropping the oldest beyond `MAX_RETENTION_RUNS`
+    pub fn append_retention_run(&self, run: &RetentionRun) -> Result<(), StorageError> {
+        let mut log = self.load_retention_log()?;
+        log.insert(0, run.clone());
+        log.truncate(MAX_RETENTION_RUNS);
+        
+        let json = serde_json::to_string(&log)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        self.adapter.store(RETENTION_LOG_KEY, json.as_bytes())
+    }
+    
+    /// Clear the retention run log
+    pub fn clear_retention_log(&self) -> Result<(), StorageError> {
+        self.adapter.remove(RETENTION_LOG_KEY)
+    }
+    
     /// Clear all stored data
     pub fn clear_all(&self) -> Result<(), StorageError> {
         self.adapter.clear()
@@ -127,3 +359,87 @@ static STORAGE: std::sync::OnceLock<StorageService> = std::sync::OnceLock::new()
 pub fn get_storage() -> &'static StorageService {
     STORAGE.get_or_init(|| StorageService::new().expect("Failed to initialize storage"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AuthConfig;
+    use crate::storage::encryption::{encrypt_legacy, KEY_TEST_LOCK};
+    
+    /// Storage service in a fresh temporary directory
+    fn temp_service() -> (StorageService, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("registry-manager-test-{}", uuid::Uuid::new_v4()));
+        let service = StorageService { adapter: DesktopStorage::at(dir.clone()).unwrap() };
+        (service, dir)
+    }
+    
+    fn registry_with_password(password: &str, encrypted_password: &str) -> RegistryConfig {
+        RegistryConfig::new(
+            "test".to_string(),
+            "https://registry.example.com".to_string(),
+            AuthConfig::BasicAuth {
+                username: "user".to_string(),
+                password: password.to_string(),
+                encrypted_password: encrypted_password.to_string(),
+            },
+        )
+    }
+    
+    fn stored_password(service: &StorageService) -> String {
+        match &service.load_registries().unwrap()[0].auth {
+            AuthConfig::BasicAuth { password, .. } => password.clone(),
+            other => panic!("unexpected auth {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn unlock_migrates_legacy_config_to_argon2id() {
+        let _guard = KEY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let (service, dir) = temp_service();
+        let legacy = vec![registry_with_password("", &encrypt_legacy("secret", "password"))];
+        let json = serde_json::to_string(&legacy).unwrap();
+        service.adapter.store(REGISTRIES_KEY, json.as_bytes()).unwrap();
+        
+        service.unlock("password").unwrap();
+        
+        assert!(service.load_kdf_params().unwrap().is_some());
+        assert!(!service.load_encrypted_registries().unwrap()[0].auth.has_legacy_secrets());
+        assert_eq!(stored_password(&service), "secret");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    
+    #[test]
+    fn unlock_rejects_wrong_password_and_accepts_a_retry() {
+        let _guard = KEY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let (service, dir) = temp_service();
+        service.unlock("password").unwrap();
+        service.save_registries(&[registry_with_password("secret", "")]).unwrap();
+        
+        assert!(service.unlock("mistyped").is_err());
+        service.unlock("password").unwrap();
+        
+        assert_eq!(stored_password(&service), "secret");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    
+    #[test]
+    fn change_password_reencrypts_credentials() {
+        let _guard = KEY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let (service, dir) = temp_service();
+        service.unlock("old").unwrap();
+        service.save_registries(&[registry_with_password("secret", "")]).unwrap();
+        let old_params = service.load_kdf_params().unwrap();
+        let old_stored = service.load_encrypted_registries().unwrap();
+        
+        assert!(service.change_password("mistyped", "new").is_err());
+        service.change_password("old", "new").unwrap();
+        
+        assert_ne!(service.load_kdf_params().unwrap(), old_params);
+        assert_ne!(service.load_encrypted_registries().unwrap()[0].auth, old_stored[0].auth);
+        assert_eq!(stored_password(&service), "secret");
+        assert!(service.unlock("old").is_err());
+        service.unlock("new").unwrap();
+        assert_eq!(stored_password(&service), "secret");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,11 @@
+//! Clipboard access through the webview
+
+use dioxus::prelude::*;
+
+/// Copy text to the system clipboard
+///
+/// Goes through the browser clipboard API, which the desktop webview provides as well.
+pub fn copy_to_clipboard(text: &str) {
+    let text = serde_json::to_string(text).unwrap_or_default();
+    document::eval(&format!("navigator.clipboard.writeText({});", text));
+}
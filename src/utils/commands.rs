@@ -0,0 +1,384 @@
+//! Command lines reproducing registry operations with curl and common registry CLIs
+
+use crate::api::{request_scope, MANIFEST_ACCEPT, TOKEN_CLIENT_ID};
+use crate::auth::token_realm;
+use reqwest::Method;
+use crate::models::{AuthConfig, RegistryConfig, TlsOptions};
+
+/// Environment variable holding the registry username
+pub const ENV_USERNAME: &str = "REGISTRY_USERNAME";
+
+/// Environment variable holding the registry password
+pub const ENV_PASSWORD: &str = "REGISTRY_PASSWORD";
+
+/// Environment variable holding a bearer or identity token
+pub const ENV_TOKEN: &str = "REGISTRY_TOKEN";
+
+/// Registry operation a command is generated for
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandTarget {
+    Catalog,
+    Tags { repo: String },
+    Manifest { repo: String, reference: String },
+    Blob { repo: String, digest: String },
+    Delete { repo: String, digest: String },
+}
+
+impl CommandTarget {
+    /// Short description for display
+    pub fn label(&self) -> String {
+        match self {
+            CommandTarget::Catalog => "List repositories".to_string(),
+            CommandTarget::Tags { repo } => format!("List tags of {}", repo),
+            CommandTarget::Manifest { repo, reference } => format!("Fetch manifest {}", image_ref(repo, reference)),
+            CommandTarget::Blob { digest, .. } => format!("Download blob {}", short(digest)),
+            CommandTarget::Delete { digest, .. } => format!("Delete manifest {}", short(digest)),
+        }
+    }
+}
+
+/// Tool a command is written for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandTool {
+    Curl,
+    DockerPull,
+    Skopeo,
+    Crane,
+    Oras,
+}
+
+impl CommandTool {
+    /// Every tool, in display order
+    pub fn all() -> [CommandTool; 5] {
+        [CommandTool::Curl, CommandTool::DockerPull, CommandTool::Skopeo, CommandTool::Crane, CommandTool::Oras]
+    }
+    
+    /// Executable name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandTool::Curl => "curl",
+            CommandTool::DockerPull => "docker",
+            CommandTool::Skopeo => "skopeo",
+            CommandTool::Crane => "crane",
+            CommandTool::Oras => "oras",
+        }
+    }
+}
+
+/// How credentials appear in generated commands
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CredentialMode {
+    /// Secrets replaced by `***`, safe to share
+    #[default]
+    Masked,
+    /// Secrets read from `$REGISTRY_USERNAME`, `$REGISTRY_PASSWORD` and `$REGISTRY_TOKEN`
+    Environment,
+}
+
+/// A generated command line
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedCommand {
+    pub tool: CommandTool,
+    pub command: String,
+}
+
+/// Every command line available for `target` on `registry`
+///
+/// Tools without an equivalent operation (e.g. `docker` for a catalog listing) are left out.
+pub fn generate_commands(registry: &RegistryConfig, target: &CommandTarget, mode: CredentialMode) -> Vec<GeneratedCommand> {
+    CommandTool::all()
+        .into_iter()
+        .filter_map(|tool| {
+            generate_command(tool, &registry.url, &registry.auth, &registry.tls, target, mode)
+                .map(|command| GeneratedCommand { tool, command })
+        })
+        .collect()
+}
+
+/// Command line for one tool, `None` when the tool cannot perform `target`
+pub fn generate_command(
+    tool: CommandTool,
+    base_url: &str,
+    auth: &AuthConfig,
+    tls: &TlsOptions,
+    target: &CommandTarget,
+    mode: CredentialMode,
+) -> Option<String> {
+    let base_url = base_url.trim_end_matches('/');
+    let host = base_url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let plain_http = base_url.starts_with("http://");
+    
+    match tool {
+        CommandTool::Curl => Some(curl_command(base_url, auth, tls, target, mode)),
+        CommandTool::DockerPull => {
+            let CommandTarget::Manifest { repo, reference } = target else {
+                return None;
+            };
+            let pull = format!("docker pull {}", shell_quote(&image_ref(&format!("{}/{}", host, repo), reference)));
+            Some(with_login(docker_login(host, auth, mode), pull))
+        }
+        CommandTool::Skopeo => {
+            let (command, image) = match target {
+                CommandTarget::Catalog | CommandTarget::Blob { .. } => return None,
+                CommandTarget::Tags { repo } => ("list-tags", format!("{}/{}", host, repo)),
+                CommandTarget::Manifest { repo, reference } => {
+                    ("inspect --raw", image_ref(&format!("{}/{}", host, repo), reference))
+                }
+                CommandTarget::Delete { repo, digest } => ("delete", format!("{}/{}@{}", host, repo, digest)),
+            };
+            let mut parts = vec!["skopeo".to_string(), command.to_string()];
+            if tls.insecure_skip_verify || plain_http {
+                parts.push("--tls-verify=false".to_string());
+            }
+            parts.extend(skopeo_credentials(auth, mode));
+            parts.push(shell_quote(&format!("docker://{}", image)));
+            Some(parts.join(" "))
+        }
+        CommandTool::Crane => {
+            let (command, arg) = match target {
+                CommandTarget::Catalog => ("catalog", host.to_string()),
+                CommandTarget::Tags { repo } => ("ls", format!("{}/{}", host, repo)),
+                CommandTarget::Manifest { repo, reference } => {
+                    ("manifest", image_ref(&format!("{}/{}", host, repo), reference))
+                }
+                CommandTarget::Blob { repo, digest } => ("blob", format!("{}/{}@{}", host, repo, digest)),
+                CommandTarget::Delete { repo, digest } => ("delete", format!("{}/{}@{}", host, repo, digest)),
+            };
+            let mut parts = vec!["crane".to_string(), command.to_string(), shell_quote(&arg)];
+            if tls.insecure_skip_verify || plain_http {
+                parts.push("--insecure".to_string());
+            }
+            if matches!(target, CommandTarget::Blob { .. }) {
+                parts.push("> blob".to_string());
+            }
+            Some(with_login(crane_login(host, auth, mode), parts.join(" ")))
+        }
+        CommandTool::Oras => {
+            let (command, arg) = match target {
+                CommandTarget::Catalog => ("repo ls", host.to_string()),
+                CommandTarget::Tags { repo } => ("repo tags", format!("{}/{}", host, repo)),
+                CommandTarget::Manifest { repo, reference } => {
+                    ("manifest fetch", image_ref(&format!("{}/{}", host, repo), reference))
+                }
+                CommandTarget::Blob { repo, digest } => ("blob fetch --output blob", format!("{}/{}@{}", host, repo, digest)),
+                CommandTarget::Delete { repo, digest } => ("manifest delete --force", format!("{}/{}@{}", host, repo, digest)),
+            };
+            let mut parts = vec!["oras".to_string(), command.to_string()];
+            if plain_http {
+                parts.push("--plain-http".to_string());
+            } else if tls.insecure_skip_verify {
+                parts.push("--insecure".to_string());
+            }
+            if !tls.ca_bundle_path.is_empty() {
+                parts.push(format!("--ca-file {}", shell_quote(&tls.ca_bundle_path)));
+            }
+            parts.extend(oras_credentials(auth, mode));
+            parts.push(shell_quote(&arg));
+            Some(parts.join(" "))
+        }
+    }
+}
+
+/// curl command calling the registry API directly
+///
+/// Registries that challenged for a bearer token get a token request against their
+/// realm first. Until a challenge has been seen, username and password are sent as
+/// basic auth, which token-based registries such as Docker Hub reject.
+fn curl_command(base_url: &str, auth: &AuthConfig, tls: &TlsOptions, target: &CommandTarget, mode: CredentialMode) -> String {
+    let (method, path, accept) = match target {
+        CommandTarget::Catalog => (Method::GET, "/v2/_catalog".to_string(), None),
+        CommandTarget::Tags { repo } => (Method::GET, format!("/v2/{}/tags/list", repo), None),
+        CommandTarget::Manifest { repo, reference } => {
+            (Method::GET, format!("/v2/{}/manifests/{}", repo, reference), Some(MANIFEST_ACCEPT))
+        }
+        CommandTarget::Blob { repo, digest } => (Method::GET, format!("/v2/{}/blobs/{}", repo, digest), None),
+        CommandTarget::Delete { repo, digest } => (Method::DELETE, format!("/v2/{}/manifests/{}", repo, digest), None),
+    };
+    
+    let realm = match auth {
+        AuthConfig::Anonymous | AuthConfig::BasicAuth { .. } | AuthConfig::IdentityToken { .. } => token_realm(base_url),
+        _ => None,
+    };
+    let token_fetch = realm.map(|realm| {
+        let mut fetch = vec!["curl".to_string(), "-sS".to_string()];
+        fetch.extend(curl_tls_flags(tls));
+        match auth {
+            AuthConfig::BasicAuth { username, .. } => {
+                fetch.push(format!("-u {}", user_password(username, mode)));
+                fetch.push("-G".to_string());
+            }
+            // Identity tokens are exchanged with an OAuth2 refresh token grant
+            AuthConfig::IdentityToken { .. } => {
+                fetch.push("--data-urlencode grant_type=refresh_token".to_string());
+                fetch.push(format!("--data-urlencode {}", secret_arg("refresh_token=", ENV_TOKEN, mode)));
+                fetch.push(format!("--data-urlencode client_id={}", TOKEN_CLIENT_ID));
+            }
+            _ => fetch.push("-G".to_string()),
+        }
+        if let Some(service) = &realm.service {
+            fetch.push(format!("--data-urlencode {}", shell_quote(&format!("service={}", service))));
+        }
+        if let Some(scope) = request_scope(&method, &path) {
+            fetch.push(format!("--data-urlencode {}", shell_quote(&format!("scope={}", scope))));
+        }
+        fetch.push(shell_quote(&realm.realm));
+        format!("TOKEN=$({} | jq -r '.token // .access_token')", fetch.join(" "))
+    });
+    
+    let mut parts = vec!["curl".to_string(), "-sS".to_string()];
+    if method != Method::GET {
+        parts.push(format!("-X {}", method));
+    }
+    parts.extend(curl_tls_flags(tls));
+    if let Some(accept) = accept {
+        parts.push(format!("-H {}", shell_quote(&format!("Accept: {}", accept))));
+    }
+    
+    match auth {
+        _ if token_fetch.is_some() => parts.push("-H \"Authorization: Bearer $TOKEN\"".to_string()),
+        AuthConfig::BasicAuth { username, .. } => parts.push(format!("-u {}", user_password(username, mode))),
+        AuthConfig::BearerToken { .. } => parts.push(format!("-H {}", secret_arg("Authorization: Bearer ", ENV_TOKEN, mode))),
+        AuthConfig::TlsCert { cert_path, key_path } => {
+            parts.push(format!("--cert {}", shell_quote(cert_path)));
+            parts.push(format!("--key {}", shell_quote(key_path)));
+        }
+        AuthConfig::Anonymous | AuthConfig::IdentityToken { .. } => {}
+    }
+    
+    if matches!(target, CommandTarget::Blob { .. }) {
+        // Blobs are usually served through a redirect to storage
+        parts.push("-L -o blob".to_string());
+    }
+    parts.push(shell_quote(&format!("{}{}", base_url, path)));
+    if token_fetch.is_none() {
+        match auth {
+            AuthConfig::BasicAuth { .. } => {
+                parts.push("# basic auth only; browse the registry once to get a token request instead".to_string())
+            }
+            AuthConfig::IdentityToken { .. } => {
+                parts.push("# identity token omitted; browse the registry once to get a token request".to_string())
+            }
+            _ => {}
+        }
+    }
+    with_login(token_fetch, parts.join(" "))
+}
+
+/// Certificate verification flags shared by registry and token requests
+fn curl_tls_flags(tls: &TlsOptions) -> Vec<String> {
+    let mut flags = Vec::new();
+    if tls.insecure_skip_verify {
+        flags.push("-k".to_string());
+    }
+    if !tls.ca_bundle_path.is_empty() {
+        flags.push(format!("--cacert {}", shell_quote(&tls.ca_bundle_path)));
+    }
+    flags
+}
+
+fn docker_login(host: &str, auth: &AuthConfig, mode: CredentialMode) -> Option<String> {
+    let AuthConfig::BasicAuth { username, .. } = auth else {
+        return None;
+    };
+    Some(match mode {
+        CredentialMode::Masked => format!("docker login {} -u {} -p '***'", host, shell_quote(username)),
+        CredentialMode::Environment => format!(
+            "echo \"${}\" | docker login {} -u \"${}\" --password-stdin",
+            ENV_PASSWORD, host, ENV_USERNAME
+        ),
+    })
+}
+
+fn crane_login(host: &str, auth: &AuthConfig, mode: CredentialMode) -> Option<String> {
+    let AuthConfig::BasicAuth { username, .. } = auth else {
+        return None;
+    };
+    Some(match mode {
+        CredentialMode::Masked => format!("crane auth login {} -u {} -p '***'", host, shell_quote(username)),
+        CredentialMode::Environment => format!(
+            "echo \"${}\" | crane auth login {} -u \"${}\" --password-stdin",
+            ENV_PASSWORD, host, ENV_USERNAME
+        ),
+    })
+}
+
+fn skopeo_credentials(auth: &AuthConfig, mode: CredentialMode) -> Vec<String> {
+    match auth {
+        AuthConfig::BasicAuth { username, .. } => vec![format!("--creds {}", user_password(username, mode))],
+        AuthConfig::BearerToken { .. } => vec![format!("--registry-token {}", secret_arg("", ENV_TOKEN, mode))],
+        AuthConfig::TlsCert { cert_path, .. } => {
+            // skopeo expects a directory holding *.cert and *.key files
+            let dir = std::path::Path::new(cert_path)
+                .parent()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default();
+            vec![format!("--cert-dir {}", shell_quote(&dir))]
+        }
+        // Read from the docker config the identity token was imported from
+        AuthConfig::Anonymous | AuthConfig::IdentityToken { .. } => Vec::new(),
+    }
+}
+
+fn oras_credentials(auth: &AuthConfig, mode: CredentialMode) -> Vec<String> {
+    match auth {
+        AuthConfig::BasicAuth { username, .. } => match mode {
+            CredentialMode::Masked => vec![format!("-u {} -p '***'", shell_quote(username))],
+            CredentialMode::Environment => vec![format!("-u \"${}\" -p \"${}\"", ENV_USERNAME, ENV_PASSWORD)],
+        },
+        AuthConfig::BearerToken { .. } => vec![format!("--registry-token {}", secret_arg("", ENV_TOKEN, mode))],
+        AuthConfig::TlsCert { cert_path, key_path } => vec![format!(
+            "--cert-file {} --key-file {}",
+            shell_quote(cert_path),
+            shell_quote(key_path)
+        )],
+        AuthConfig::IdentityToken { .. } => vec![format!("--identity-token {}", secret_arg("", ENV_TOKEN, mode))],
+        AuthConfig::Anonymous => Vec::new(),
+    }
+}
+
+/// `user:password` argument, masked or read from the environment
+fn user_password(username: &str, mode: CredentialMode) -> String {
+    match mode {
+        CredentialMode::Masked => shell_quote(&format!("{}:***", username)),
+        CredentialMode::Environment => format!("\"${}:${}\"", ENV_USERNAME, ENV_PASSWORD),
+    }
+}
+
+/// Argument ending in a secret, masked or read from `env`
+fn secret_arg(prefix: &str, env: &str, mode: CredentialMode) -> String {
+    match mode {
+        CredentialMode::Masked => shell_quote(&format!("{}***", prefix)),
+        CredentialMode::Environment => format!("\"{}${}\"", prefix, env),
+    }
+}
+
+fn with_login(login: Option<String>, command: String) -> String {
+    match login {
+        Some(login) => format!("{} && {}", login, command),
+        None => command,
+    }
+}
+
+/// `name:tag` or `name@digest`
+fn image_ref(name: &str, reference: &str) -> String {
+    if reference.contains(':') {
+        format!("{}@{}", name, reference)
+    } else {
+        format!("{}:{}", name, reference)
+    }
+}
+
+fn short(digest: &str) -> &str {
+    let hex = digest.split_once(':').map(|(_, h)| h).unwrap_or(digest);
+    &hex[..hex.len().min(12)]
+}
+
+/// Single-quote a value for POSIX shells
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:@".contains(c)) {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
@@ -1,31 +1,16 @@
 //! cURL command generation
 
-use crate::models::{ApiRequestInfo, AuthConfig};
+use crate::models::{ApiRequestInfo, AuthConfig, TlsOptions};
+use super::{generate_command, CommandTarget, CommandTool, CredentialMode};
 
 /// Generate a cURL command for fetching a manifest
 pub fn generate_curl_command(base_url: &str, repo: &str, tag: &str, auth: &AuthConfig) -> String {
-    let url = format!("{}/v2/{}/manifests/{}", base_url, repo, tag);
-    let mut parts = vec!["curl".to_string()];
-    
-    // Add Accept header for manifest
-    parts.push("-H".to_string());
-    parts.push("'Accept: application/vnd.docker.distribution.manifest.v2+json'".to_string());
-    
-    // Add auth header
-    match auth {
-        AuthConfig::BasicAuth { username, .. } => {
-            parts.push("-H".to_string());
-            parts.push(format!("'Authorization: Basic <{}>***'", username));
-        }
-        AuthConfig::BearerToken { .. } => {
-            parts.push("-H".to_string());
-            parts.push("'Authorization: Bearer ***'".to_string());
-        }
-        AuthConfig::Anonymous | AuthConfig::TlsCert { .. } => {}
-    }
-    
-    parts.push(format!("'{}'", url));
-    parts.join(" ")
+    let target = CommandTarget::Manifest {
+        repo: repo.to_string(),
+        reference: tag.to_string(),
+    };
+    generate_command(CommandTool::Curl, base_url, auth, &TlsOptions::default(), &target, CredentialMode::Masked)
+        .unwrap_or_default()
 }
 
 /// Generate a cURL command from API request info
@@ -68,23 +53,12 @@ pub fn create_request_info(
     url: &str,
     headers: Vec<(String, String)>,
 ) -> ApiRequestInfo {
-    let curl_command = generate_curl_command_from_info(&ApiRequestInfo {
-        method: method.to_string(),
-        url: url.to_string(),
-        headers: headers.clone(),
-        status_code: None,
-        response_headers: None,
-        response_body: None,
-        curl_command: String::new(),
-    });
-    
-    ApiRequestInfo {
+    let mut info = ApiRequestInfo {
         method: method.to_string(),
         url: url.to_string(),
         headers,
-        status_code: None,
-        response_headers: None,
-        response_body: None,
-        curl_command,
-    }
+        ..Default::default()
+    };
+    info.curl_command = generate_curl_command_from_info(&info);
+    info
 }
@@ -0,0 +1,26 @@
+//! Content digest utilities
+
+use sha2::{Digest, Sha256};
+
+/// Compute the `sha256:<hex>` digest of content
+pub fn sha256_digest(data: &[u8]) -> String {
+    let hash = Sha256::digest(data);
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// Verify content against an expected digest
+///
+/// Returns the computed digest on mismatch. Algorithms other than sha256 cannot be
+/// checked and are accepted as-is.
+pub fn verify_digest(data: &[u8], expected: &str) -> Result<(), String> {
+    if !expected.starts_with("sha256:") {
+        return Ok(());
+    }
+    let actual = sha256_digest(data);
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(actual)
+    }
+}
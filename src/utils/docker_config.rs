@@ -0,0 +1,268 @@
+//! Import registries and credentials from the docker CLI config
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use crate::models::{AuthConfig, ConnectionStatus, RegistryConfig, TlsOptions, DEFAULT_MAX_RETRIES, IDENTITY_TOKEN_USERNAME};
+
+/// Key the docker CLI stores Docker Hub credentials under
+const DOCKER_HUB_SERVER: &str = "https://index.docker.io/v1/";
+
+/// Registry API endpoint of Docker Hub
+const DOCKER_HUB_URL: &str = "https://registry-1.docker.io";
+
+/// Relevant parts of `~/.docker/config.json`
+#[derive(Debug, Default, Deserialize)]
+struct DockerConfigFile {
+    #[serde(default)]
+    auths: HashMap<String, DockerAuthEntry>,
+    #[serde(default, rename = "credHelpers")]
+    cred_helpers: HashMap<String, String>,
+    #[serde(default, rename = "credsStore")]
+    creds_store: Option<String>,
+}
+
+/// Entry of the `auths` map
+#[derive(Debug, Default, Deserialize)]
+struct DockerAuthEntry {
+    /// Base64 encoded `username:password`
+    #[serde(default)]
+    auth: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    /// OAuth2 refresh token
+    #[serde(default)]
+    identitytoken: Option<String>,
+    /// Bearer token sent to the registry as is
+    #[serde(default)]
+    registrytoken: Option<String>,
+}
+
+/// Output of `docker-credential-<helper> get`
+#[derive(Debug, Deserialize)]
+struct HelperCredentials {
+    #[serde(rename = "Username", default)]
+    username: String,
+    #[serde(rename = "Secret", default)]
+    secret: String,
+}
+
+/// Registries read from a docker config
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DockerConfigImport {
+    /// One registry per server, with credentials when they could be resolved
+    pub registries: Vec<RegistryConfig>,
+    /// Servers whose credentials could not be read, with the reason
+    pub warnings: Vec<String>,
+}
+
+/// Path of the docker CLI config: `$DOCKER_CONFIG/config.json`, else `~/.docker/config.json`
+pub fn default_docker_config_path() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("DOCKER_CONFIG") {
+        return Some(PathBuf::from(dir).join("config.json"));
+    }
+    directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".docker").join("config.json"))
+}
+
+/// Read a docker config file and turn its servers into registries
+///
+/// Inline `auths` credentials are decoded directly. With `use_helpers`, servers
+/// without inline credentials are resolved through `credHelpers` or `credsStore`
+/// by running the matching `docker-credential-*` program.
+pub async fn import_docker_config(path: &Path, use_helpers: bool) -> Result<DockerConfigImport, String> {
+    let json = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let config: DockerConfigFile = serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid docker config: {}", e))?;
+    
+    // Sorted and de-duplicated: servers from auths, credHelpers and the default store
+    let mut servers: BTreeMap<String, Option<String>> = BTreeMap::new();
+    for server in config.auths.keys() {
+        servers.insert(server.clone(), config.creds_store.clone());
+    }
+    for (server, helper) in &config.cred_helpers {
+        servers.insert(server.clone(), Some(helper.clone()));
+    }
+    
+    let mut import = DockerConfigImport::default();
+    
+    if use_helpers {
+        if let Some(store) = &config.creds_store {
+            match helper_list(store).await {
+                Ok(listed) => {
+                    for server in listed {
+                        servers.entry(server).or_insert_with(|| Some(store.clone()));
+                    }
+                }
+                Err(e) => import.warnings.push(format!("credsStore {}: {}", store, e)),
+            }
+        }
+    }
+    
+    for (server, helper) in servers {
+        // Docker Hub is listed under several keys
+        let (name, url) = registry_endpoint(&server);
+        if import.registries.iter().any(|r| r.url == url) {
+            continue;
+        }
+        
+        let inline = match config.auths.get(&server).map(inline_auth) {
+            Some(Ok(auth)) => auth,
+            // A credential helper may still provide credentials
+            Some(Err(e)) => {
+                import.warnings.push(format!("{}: ignoring invalid inline credentials, {}", server, e));
+                None
+            }
+            None => None,
+        };
+        let auth = match (inline, helper) {
+            (Some(auth), _) => auth,
+            (None, Some(helper)) if use_helpers => match helper_get(&helper, &server).await {
+                Ok(auth) => auth,
+                Err(e) => {
+                    import.warnings.push(format!("{}: {}", server, e));
+                    AuthConfig::Anonymous
+                }
+            },
+            (None, Some(helper)) => {
+                import.warnings.push(format!("{}: credentials are kept by docker-credential-{}", server, helper));
+                AuthConfig::Anonymous
+            }
+            (None, None) => AuthConfig::Anonymous,
+        };
+        
+        import.registries.push(RegistryConfig {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            url,
+            auth,
+            tls: TlsOptions::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            status: ConnectionStatus::Unknown,
+        });
+    }
+    
+    Ok(import)
+}
+
+/// Credentials stored directly in an `auths` entry
+///
+/// `Ok(None)` when the entry holds none; an error when the `auth` field cannot be
+/// decoded, which the import reports as a warning.
+fn inline_auth(entry: &DockerAuthEntry) -> Result<Option<AuthConfig>, String> {
+    if let Some(token) = entry.identitytoken.as_deref().filter(|t| !t.is_empty()) {
+        return Ok(Some(identity_token(token)));
+    }
+    if let Some(token) = entry.registrytoken.as_deref().filter(|t| !t.is_empty()) {
+        return Ok(Some(AuthConfig::BearerToken {
+            token: token.to_string(),
+            encrypted_token: String::new(),
+        }));
+    }
+    if let Some(encoded) = entry.auth.as_deref().filter(|a| !a.is_empty()) {
+        let decoded = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("auth is not valid base64 ({})", e))?;
+        let credentials = String::from_utf8(decoded)
+            .map_err(|_| "auth is not valid UTF-8".to_string())?;
+        let (username, password) = credentials
+            .split_once(':')
+            .ok_or_else(|| "auth is not in user:password form".to_string())?;
+        return Ok(Some(basic(username, password)));
+    }
+    match (&entry.username, &entry.password) {
+        (Some(username), Some(password)) if !username.is_empty() => Ok(Some(basic(username, password))),
+        _ => Ok(None),
+    }
+}
+
+/// Credentials returned by `docker-credential-<helper> get`
+async fn helper_get(helper: &str, server: &str) -> Result<AuthConfig, String> {
+    let output = run_helper(helper, "get", server).await?;
+    let credentials: HelperCredentials = serde_json::from_slice(&output)
+        .map_err(|e| format!("Unexpected output from docker-credential-{}: {}", helper, e))?;
+    
+    // The helper protocol marks identity tokens with the "<token>" username
+    if credentials.username == IDENTITY_TOKEN_USERNAME {
+        return Ok(identity_token(&credentials.secret));
+    }
+    Ok(basic(&credentials.username, &credentials.secret))
+}
+
+/// Servers a credential store has entries for
+async fn helper_list(helper: &str) -> Result<Vec<String>, String> {
+    let output = run_helper(helper, "list", "").await?;
+    let listed: HashMap<String, String> = serde_json::from_slice(&output)
+        .map_err(|e| format!("Unexpected output from docker-credential-{}: {}", helper, e))?;
+    Ok(listed.into_keys().collect())
+}
+
+/// Run a credential helper action, writing `input` to its stdin
+async fn run_helper(helper: &str, action: &str, input: &str) -> Result<Vec<u8>, String> {
+    let program = format!("docker-credential-{}", helper);
+    let mut child = Command::new(&program)
+        .arg(action)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Cannot run {}: {}", program, e))?;
+    
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .await
+            .map_err(|e| format!("Cannot write to {}: {}", program, e))?;
+    }
+    
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("{} failed: {}", program, e))?;
+    if !output.status.success() {
+        // Helpers report errors such as "credentials not found in native keychain" on stdout
+        let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Err(if message.is_empty() {
+            format!("{} exited with {}", program, output.status)
+        } else {
+            message
+        });
+    }
+    Ok(output.stdout)
+}
+
+/// Display name and API URL for a docker config server key
+fn registry_endpoint(server: &str) -> (String, String) {
+    if server == DOCKER_HUB_SERVER || server.contains("index.docker.io") || server == "docker.io" {
+        return ("Docker Hub".to_string(), DOCKER_HUB_URL.to_string());
+    }
+    
+    let (scheme, rest) = match server.split_once("://") {
+        Some((scheme, rest)) => (scheme, rest),
+        None => ("https", server),
+    };
+    let host = rest.split('/').next().unwrap_or(rest);
+    (host.to_string(), format!("{}://{}", scheme, host))
+}
+
+fn identity_token(token: &str) -> AuthConfig {
+    AuthConfig::IdentityToken {
+        token: token.to_string(),
+        encrypted_token: String::new(),
+    }
+}
+
+fn basic(username: &str, password: &str) -> AuthConfig {
+    AuthConfig::BasicAuth {
+        username: username.to_string(),
+        password: password.to_string(),
+        encrypted_password: String::new(),
+    }
+}
@@ -1,6 +1,6 @@
 //! Export/import utilities
 
-use crate::models::{AuthConfig, RegistryConfig, TagInfo};
+use crate::models::{AuthConfig, DeletionResult, RegistryConfig, TagInfo, TlsOptions};
 use serde::{Deserialize, Serialize};
 
 /// Registry config for export (without sensitive data)
@@ -10,6 +10,10 @@ pub struct ExportedRegistryConfig {
     pub name: String,
     pub url: String,
     pub auth_type: String,
+    #[serde(default = "TlsOptions::legacy")]
+    pub tls: TlsOptions,
+    #[serde(default = "crate::models::default_max_retries")]
+    pub max_retries: u32,
 }
 
 /// Export registry configurations to JSON (excluding credentials)
@@ -25,7 +29,10 @@ pub fn export_registries(registries: &[RegistryConfig]) -> String {
                 AuthConfig::BasicAuth { username, .. } => format!("basic:{}", username),
                 AuthConfig::BearerToken { .. } => "bearer".to_string(),
                 AuthConfig::TlsCert { .. } => "tls".to_string(),
+                AuthConfig::IdentityToken { .. } => "identity_token".to_string(),
             },
+            tls: r.tls.clone(),
+            max_retries: r.max_retries,
         })
         .collect();
     
@@ -48,13 +55,49 @@ pub fn export_tags_json(tags: &[TagInfo]) -> String {
 
 /// Export tags to CSV format
 pub fn export_tags_csv(tags: &[TagInfo]) -> String {
-    let mut csv = String::from("name,digest,size\n");
+    let mut csv = String::from("name,digest,size,created\n");
     for tag in tags {
-        csv.push_str(&format!("{},{},{}\n", tag.name, tag.digest, tag.size));
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            tag.name,
+            tag.digest,
+            tag.size,
+            tag.created.as_deref().unwrap_or("")
+        ));
     }
     csv
 }
 
+/// Export a deletion report to JSON format
+pub fn export_deletion_report_json(result: &DeletionResult) -> String {
+    serde_json::to_string_pretty(&result.entries).unwrap_or_default()
+}
+
+/// Export a deletion report to CSV format
+pub fn export_deletion_report_csv(result: &DeletionResult) -> String {
+    let mut csv = String::from("tag,digest,status,error,attempts\n");
+    for entry in &result.entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&entry.tag),
+            entry.digest,
+            entry.status.as_str(),
+            csv_field(entry.error.as_deref().unwrap_or("")),
+            entry.attempts
+        ));
+    }
+    csv
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 /// Check if exported data contains all tags
 pub fn export_contains_all_tags(export: &str, tags: &[TagInfo]) -> bool {
     tags.iter().all(|tag| {
@@ -77,6 +120,8 @@ pub fn import_registries(json: &str) -> Result<Vec<RegistryConfig>, String> {
                 name: e.name,
                 url: e.url,
                 auth,
+                tls: e.tls,
+                max_retries: e.max_retries,
                 status: crate::models::ConnectionStatus::Unknown,
             }
         })
@@ -100,6 +145,11 @@ fn parse_auth_type(auth_type: &str) -> AuthConfig {
             token: String::new(), // Needs to be filled in
             encrypted_token: String::new(),
         }
+    } else if auth_type == "identity_token" {
+        AuthConfig::IdentityToken {
+            token: String::new(), // Needs to be filled in
+            encrypted_token: String::new(),
+        }
     } else if auth_type == "tls" {
         AuthConfig::TlsCert {
             cert_path: String::new(),
@@ -1,5 +1,7 @@
 //! String filtering utilities
 
+use regex::{Regex, RegexBuilder};
+
 /// Filter a list of strings by a search term (case-insensitive)
 pub fn filter_strings<'a>(items: &'a [String], search: &str) -> Vec<&'a String> {
     if search.is_empty() {
@@ -20,3 +22,86 @@ pub fn filter_strings_owned(items: &[String], search: &str) -> Vec<String> {
         .cloned()
         .collect()
 }
+
+/// Tag filter parsed from the search box
+///
+/// - `/pattern/` or `re:pattern` is a regular expression
+/// - a term containing `*` or `?` is a glob matched against the whole name (`release-*`)
+/// - anything else is a substring match
+///
+/// All forms are case-insensitive.
+#[derive(Clone, Debug)]
+pub enum NameFilter {
+    All,
+    Substring(String),
+    Pattern(Regex),
+}
+
+impl PartialEq for NameFilter {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (NameFilter::All, NameFilter::All) => true,
+            (NameFilter::Substring(a), NameFilter::Substring(b)) => a == b,
+            (NameFilter::Pattern(a), NameFilter::Pattern(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl NameFilter {
+    /// Parse a search term into a filter
+    pub fn parse(search: &str) -> Result<Self, String> {
+        let search = search.trim();
+        if search.is_empty() {
+            return Ok(NameFilter::All);
+        }
+        
+        let regex_source = search
+            .strip_prefix("re:")
+            .map(str::to_string)
+            .or_else(|| {
+                search
+                    .strip_prefix('/')
+                    .and_then(|s| s.strip_suffix('/'))
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+            })
+            .or_else(|| {
+                search
+                    .contains(['*', '?'])
+                    .then(|| glob_to_regex(search))
+            });
+        
+        match regex_source {
+            Some(source) => RegexBuilder::new(&source)
+                .case_insensitive(true)
+                .build()
+                .map(NameFilter::Pattern)
+                .map_err(|e| format!("Invalid pattern: {}", e)),
+            None => Ok(NameFilter::Substring(search.to_lowercase())),
+        }
+    }
+    
+    /// Check if a name matches the filter
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NameFilter::All => true,
+            NameFilter::Substring(term) => name.to_lowercase().contains(term),
+            NameFilter::Pattern(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// Convert a glob (`*`, `?`) into an anchored regular expression
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
@@ -4,12 +4,28 @@ mod filter;
 mod sort;
 mod size;
 mod curl;
+mod commands;
+mod clipboard;
 mod export;
+#[cfg(not(target_arch = "wasm32"))]
+mod docker_config;
 mod history;
+mod digest;
+mod retention;
+mod shared_digest;
+mod timer;
 
 pub use filter::*;
 pub use sort::*;
 pub use size::*;
 pub use curl::*;
+pub use commands::*;
+pub use clipboard::*;
 pub use export::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use docker_config::*;
 pub use history::*;
+pub use digest::*;
+pub use retention::*;
+pub use shared_digest::*;
+pub use timer::*;
@@ -0,0 +1,242 @@
+//! Retention policy evaluation
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use crate::models::{RetentionAction, RetentionPlan, RetentionPolicy, RetentionRule, TagDecision, TagInfo};
+use super::{created_timestamp, parse_tag_version, NameFilter, TagVersion};
+
+/// Unresolved tags named in the reason of a blocked delete
+const BLOCKED_SAMPLE_SIZE: usize = 3;
+
+/// Evaluate a retention policy against the enriched tags of one repository
+///
+/// Keep reasons always win over delete reasons. Tags without a creation date or
+/// digest are never deleted, and neither is a tag whose digest is shared with a
+/// kept tag, since deleting the manifest would remove both. A tag whose digest is
+/// unknown could share any manifest, so nothing is deleted while one remains.
+pub fn evaluate_retention(
+    policy: &RetentionPolicy,
+    tags: &[TagInfo],
+    now: DateTime<Utc>,
+) -> Result<RetentionPlan, String> {
+    let mut keep: Vec<Option<String>> = vec![None; tags.len()];
+    let mut delete: Vec<Option<String>> = vec![None; tags.len()];
+    
+    for rule in &policy.rules {
+        match rule {
+            RetentionRule::KeepReleases => {
+                // Variant suffixes such as `-alpine` count as releases, shown in the reason
+                for (i, tag) in tags.iter().enumerate() {
+                    match parse_tag_version(&tag.name) {
+                        Some(version) if version.is_prerelease() => {}
+                        Some(TagVersion { pre: Some(variant), .. }) => {
+                            keep[i].get_or_insert_with(|| format!("Semver release ({} variant)", variant));
+                        }
+                        Some(_) => {
+                            keep[i].get_or_insert_with(|| "Semver release".to_string());
+                        }
+                        None => {}
+                    }
+                }
+            }
+            RetentionRule::KeepNewest { pattern, count } => {
+                let filter = NameFilter::parse(pattern)?;
+                let mut dated: Vec<(usize, i64)> = Vec::new();
+                for (i, tag) in tags.iter().enumerate() {
+                    if !filter.matches(&tag.name) {
+                        continue;
+                    }
+                    match created_timestamp(tag) {
+                        Some(ts) => dated.push((i, ts)),
+                        None => {
+                            keep[i].get_or_insert_with(|| "Creation date unknown".to_string());
+                        }
+                    }
+                }
+                
+                // Newest first
+                dated.sort_by_key(|&(_, ts)| std::cmp::Reverse(ts));
+                for (rank, (i, _)) in dated.into_iter().enumerate() {
+                    if rank < *count {
+                        keep[i].get_or_insert_with(|| format!("Among newest {} matching '{}'", count, pattern));
+                    } else {
+                        delete[i].get_or_insert_with(|| format!("Beyond newest {} matching '{}'", count, pattern));
+                    }
+                }
+            }
+            RetentionRule::DeleteOlderThan { pattern, days } => {
+                let filter = NameFilter::parse(pattern)?;
+                let cutoff = (now - Duration::days(*days as i64)).timestamp();
+                for (i, tag) in tags.iter().enumerate() {
+                    if !filter.matches(&tag.name) {
+                        continue;
+                    }
+                    match created_timestamp(tag) {
+                        Some(ts) if ts < cutoff => {
+                            delete[i].get_or_insert_with(|| format!("Older than {} days", days));
+                        }
+                        Some(_) => {}
+                        None => {
+                            keep[i].get_or_insert_with(|| "Creation date unknown".to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    let mut decisions: Vec<TagDecision> = tags
+        .iter()
+        .zip(keep.into_iter().zip(delete))
+        .map(|(tag, (keep, delete))| {
+            let (action, reason) = match (keep, delete) {
+                (Some(reason), _) => (RetentionAction::Keep, reason),
+                (None, Some(_)) if tag.digest.is_empty() => {
+                    (RetentionAction::Keep, "Digest unknown".to_string())
+                }
+                (None, Some(reason)) => (RetentionAction::Delete, reason),
+                (None, None) => (RetentionAction::Keep, "No rule matched".to_string()),
+            };
+            TagDecision {
+                tag: tag.clone(),
+                action,
+                reason,
+            }
+        })
+        .collect();
+    
+    // Deleting a manifest removes every tag pointing at it
+    let mut kept_by_digest: HashMap<String, Vec<String>> = HashMap::new();
+    for decision in decisions.iter().filter(|d| d.action == RetentionAction::Keep) {
+        if !decision.tag.digest.is_empty() {
+            kept_by_digest
+                .entry(decision.tag.digest.clone())
+                .or_default()
+                .push(decision.tag.name.clone());
+        }
+    }
+    let unresolved: Vec<String> = tags
+        .iter()
+        .filter(|t| t.digest.is_empty())
+        .map(|t| t.name.clone())
+        .collect();
+    let blocked = (!unresolved.is_empty()).then(|| blocked_reason(&unresolved));
+    for decision in decisions.iter_mut().filter(|d| d.action == RetentionAction::Delete) {
+        if let Some(reason) = &blocked {
+            decision.action = RetentionAction::Keep;
+            decision.reason = reason.clone();
+        } else if let Some(kept) = kept_by_digest.get(&decision.tag.digest) {
+            decision.action = RetentionAction::Keep;
+            decision.reason = format!("Shares digest with kept tag {}", kept.join(", "));
+        }
+    }
+    
+    Ok(RetentionPlan { decisions })
+}
+
+/// Reason for deletes blocked by unresolved tags, naming only the first few
+fn blocked_reason(unresolved: &[String]) -> String {
+    let sample = unresolved[..unresolved.len().min(BLOCKED_SAMPLE_SIZE)].join(", ");
+    match unresolved.len().saturating_sub(BLOCKED_SAMPLE_SIZE) {
+        0 => format!("Blocked: digest unknown for {} tag(s): {}", unresolved.len(), sample),
+        more => format!(
+            "Blocked: digest unknown for {} tags: {} and {} more",
+            unresolved.len(),
+            sample,
+            more
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap()
+    }
+    
+    fn tag(name: &str, digest: &str, age_days: Option<i64>) -> TagInfo {
+        TagInfo {
+            name: name.to_string(),
+            digest: digest.to_string(),
+            size: 0,
+            created: age_days.map(|days| (now() - Duration::days(days)).to_rfc3339()),
+        }
+    }
+    
+    fn policy(rules: Vec<RetentionRule>) -> RetentionPolicy {
+        RetentionPolicy { rules }
+    }
+    
+    fn decision<'a>(plan: &'a RetentionPlan, name: &str) -> &'a TagDecision {
+        plan.decisions.iter().find(|d| d.tag.name == name).unwrap()
+    }
+    
+    #[test]
+    fn keep_rules_win_over_delete_rules() {
+        let tags = vec![tag("1.0.0", "sha256:a", Some(100)), tag("dev-1", "sha256:b", Some(100))];
+        let policy = policy(vec![
+            RetentionRule::DeleteOlderThan { pattern: "*".to_string(), days: 30 },
+            RetentionRule::KeepReleases,
+        ]);
+        
+        let plan = evaluate_retention(&policy, &tags, now()).unwrap();
+        
+        assert_eq!(decision(&plan, "1.0.0").action, RetentionAction::Keep);
+        assert_eq!(decision(&plan, "1.0.0").reason, "Semver release");
+        assert_eq!(decision(&plan, "dev-1").action, RetentionAction::Delete);
+    }
+    
+    #[test]
+    fn tag_sharing_digest_with_kept_tag_is_not_deleted() {
+        let tags = vec![tag("1.0.0", "sha256:a", Some(100)), tag("latest", "sha256:a", Some(100))];
+        let policy = policy(vec![
+            RetentionRule::KeepReleases,
+            RetentionRule::DeleteOlderThan { pattern: "*".to_string(), days: 30 },
+        ]);
+        
+        let plan = evaluate_retention(&policy, &tags, now()).unwrap();
+        
+        let latest = decision(&plan, "latest");
+        assert_eq!(latest.action, RetentionAction::Keep);
+        assert_eq!(latest.reason, "Shares digest with kept tag 1.0.0");
+        assert!(plan.digests_to_delete().is_empty());
+    }
+    
+    #[test]
+    fn unknown_digest_blocks_all_deletes() {
+        let tags = vec![
+            tag("old-1", "sha256:a", Some(100)),
+            tag("old-2", "sha256:b", Some(100)),
+            tag("unresolved", "", Some(1)),
+        ];
+        let policy = policy(vec![RetentionRule::DeleteOlderThan { pattern: "old-*".to_string(), days: 30 }]);
+        
+        let plan = evaluate_retention(&policy, &tags, now()).unwrap();
+        
+        assert!(plan.to_delete().is_empty());
+        assert_eq!(plan.unresolved().len(), 1);
+        assert_eq!(decision(&plan, "old-1").reason, "Blocked: digest unknown for 1 tag(s): unresolved");
+    }
+    
+    #[test]
+    fn blocked_reason_names_only_a_sample() {
+        let unresolved: Vec<String> = (1..=5).map(|i| format!("t{}", i)).collect();
+        assert_eq!(blocked_reason(&unresolved), "Blocked: digest unknown for 5 tags: t1, t2, t3 and 2 more");
+    }
+    
+    #[test]
+    fn tag_without_creation_date_is_kept() {
+        let tags = vec![tag("old", "sha256:a", Some(100)), tag("undated", "sha256:b", None)];
+        let policy = policy(vec![RetentionRule::DeleteOlderThan { pattern: "*".to_string(), days: 30 }]);
+        
+        let plan = evaluate_retention(&policy, &tags, now()).unwrap();
+        
+        assert_eq!(decision(&plan, "old").action, RetentionAction::Delete);
+        let undated = decision(&plan, "undated");
+        assert_eq!(undated.action, RetentionAction::Keep);
+        assert_eq!(undated.reason, "Creation date unknown");
+    }
+}
@@ -0,0 +1,63 @@
+//! Shared-digest detection for tag deletion
+//!
+//! The registry deletes manifests, not tags: removing one tag by digest removes
+//! every tag of the repository that points at the same manifest.
+
+use std::collections::BTreeMap;
+use crate::models::TagInfo;
+
+/// Manifest that a deletion targets, with the tags it takes down
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigestGroup {
+    pub digest: String,
+    /// Tags the user selected for deletion
+    pub selected: Vec<String>,
+    /// Other tags pointing at the same manifest that will disappear too
+    pub collateral: Vec<String>,
+}
+
+impl DigestGroup {
+    /// Every tag removed by deleting this manifest
+    pub fn all_tags(&self) -> Vec<String> {
+        self.selected.iter().chain(&self.collateral).cloned().collect()
+    }
+}
+
+/// Map each known digest to the tags pointing at it, skipping tags without a digest
+pub fn tags_by_digest(tags: &[TagInfo]) -> BTreeMap<String, Vec<String>> {
+    let mut map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for tag in tags.iter().filter(|t| !t.digest.is_empty()) {
+        map.entry(tag.digest.clone()).or_default().push(tag.name.clone());
+    }
+    map
+}
+
+/// Group selected tags by manifest digest and list the unselected tags sharing each one
+///
+/// Groups are returned in selection order. Selected tags without a known digest are
+/// left out; the caller reports them separately.
+pub fn group_by_digest(tags: &[TagInfo], selected: &[String]) -> Vec<DigestGroup> {
+    let map = tags_by_digest(tags);
+    let mut groups: Vec<DigestGroup> = Vec::new();
+    
+    for name in selected {
+        let Some(tag) = tags.iter().find(|t| &t.name == name && !t.digest.is_empty()) else {
+            continue;
+        };
+        if groups.iter().any(|g| g.digest == tag.digest) {
+            continue;
+        }
+        
+        let sharing = map.get(&tag.digest).cloned().unwrap_or_default();
+        let (selected_tags, collateral): (Vec<String>, Vec<String>) = sharing
+            .into_iter()
+            .partition(|t| selected.contains(t));
+        groups.push(DigestGroup {
+            digest: tag.digest.clone(),
+            selected: selected_tags,
+            collateral,
+        });
+    }
+    
+    groups
+}
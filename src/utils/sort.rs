@@ -1,8 +1,11 @@
 //! Sorting utilities
 
+use crate::models::TagInfo;
+use std::cmp::Ordering;
+
 /// Sort strings alphabetically (case-insensitive)
 pub fn sort_alphabetically(items: &mut [String]) {
-    items.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+    items.sort_by_key(|a| a.to_lowercase());
 }
 
 /// Sort strings alphabetically and return a new vector
@@ -16,3 +19,195 @@ pub fn sorted_alphabetically(items: &[String]) -> Vec<String> {
 pub fn is_sorted_alphabetically(items: &[String]) -> bool {
     items.windows(2).all(|w| w[0].to_lowercase() <= w[1].to_lowercase())
 }
+
+/// Tag list ordering options
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagSortOrder {
+    /// Alphabetical (case-insensitive)
+    #[default]
+    Name,
+    /// Highest version first; non-version tags last
+    Version,
+    /// Most recently created first; unknown dates last
+    Created,
+    /// Largest first; unknown sizes last
+    Size,
+}
+
+/// Version parsed from a tag such as `1.10.0`, `v2.3` or `1.2.3-rc.1`
+///
+/// Equality follows the ordering, so suffixes differing only in case are equal.
+#[derive(Clone, Debug)]
+pub struct TagVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release or suffix after `-` (e.g. `rc.1`, `alpine`)
+    pub pre: Option<String>,
+}
+
+impl Ord for TagVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                // A release sorts after its pre-releases
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_natural(a, b),
+            })
+    }
+}
+
+impl TagVersion {
+    /// Whether the suffix marks a pre-release (`rc.1`, `beta2`) rather than a
+    /// build variant of a release (`alpine`, `slim-bookworm`)
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.as_deref().map(is_prerelease_suffix).unwrap_or(false)
+    }
+}
+
+impl PartialEq for TagVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TagVersion {}
+
+impl PartialOrd for TagVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parse a tag as a version (leading `v` allowed, missing minor/patch default to 0)
+pub fn parse_tag_version(tag: &str) -> Option<TagVersion> {
+    let tag = tag.strip_prefix('v').or_else(|| tag.strip_prefix('V')).unwrap_or(tag);
+    let (core, pre) = match tag.split_once('-') {
+        Some((core, pre)) => (core, Some(pre.to_string())),
+        None => (tag, None),
+    };
+    // Build metadata does not affect ordering
+    let core = core.split('+').next().unwrap_or(core);
+    
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let mut numbers = [0u64; 3];
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        numbers[i] = part.parse().ok()?;
+    }
+    
+    Some(TagVersion {
+        major: numbers[0],
+        minor: numbers[1],
+        patch: numbers[2],
+        pre: pre.filter(|p| !p.is_empty()),
+    })
+}
+
+/// Check if a tag is a release version, including variants such as `1.2.3-alpine`
+pub fn is_release_version(tag: &str) -> bool {
+    parse_tag_version(tag).map(|v| !v.is_prerelease()).unwrap_or(false)
+}
+
+/// Identifiers that mark a suffix as a pre-release
+const PRERELEASE_MARKERS: &[&str] = &[
+    "alpha", "beta", "rc", "pre", "preview", "dev", "snapshot", "nightly", "canary", "next", "a", "b",
+];
+
+/// Whether a version suffix is a pre-release: numeric (`1.2.3-1`) or starting with
+/// a marker such as `rc`, optionally followed by digits (`rc1`, `beta.2`)
+fn is_prerelease_suffix(pre: &str) -> bool {
+    let first = pre.split(['.', '-', '_']).next().unwrap_or(pre).to_ascii_lowercase();
+    let word = first.trim_end_matches(|c: char| c.is_ascii_digit());
+    word.is_empty() || PRERELEASE_MARKERS.contains(&word)
+}
+
+/// Compare strings treating digit runs as numbers (`rc.9` < `rc.10`)
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let take_number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut digits = String::new();
+                    while let Some(c) = chars.peek().copied().filter(|c| c.is_ascii_digit()) {
+                        digits.push(c);
+                        chars.next();
+                    }
+                    digits.parse::<u128>().unwrap_or(u128::MAX)
+                };
+                let ordering = take_number(&mut a_chars).cmp(&take_number(&mut b_chars));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// Sort tags in place by the given order
+pub fn sort_tags(tags: &mut [TagInfo], order: TagSortOrder) {
+    match order {
+        TagSortOrder::Name => tags.sort_by_key(|t| t.name.to_lowercase()),
+        TagSortOrder::Version => tags.sort_by(|a, b| {
+            match (parse_tag_version(&a.name), parse_tag_version(&b.name)) {
+                (Some(va), Some(vb)) => vb.cmp(&va),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => compare_natural(&a.name, &b.name),
+            }
+        }),
+        TagSortOrder::Created => tags.sort_by(|a, b| {
+            match (created_timestamp(a), created_timestamp(b)) {
+                (Some(ta), Some(tb)) => tb.cmp(&ta),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            }
+        }),
+        TagSortOrder::Size => tags.sort_by(|a, b| {
+            // Size 0 means not resolved yet
+            match (a.size, b.size) {
+                (0, 0) => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                (0, _) => Ordering::Greater,
+                (_, 0) => Ordering::Less,
+                (sa, sb) => sb.cmp(&sa),
+            }
+        }),
+    }
+}
+
+/// Sort tags and return a new vector
+pub fn sorted_tags(tags: &[TagInfo], order: TagSortOrder) -> Vec<TagInfo> {
+    let mut sorted = tags.to_vec();
+    sort_tags(&mut sorted, order);
+    sorted
+}
+
+/// Parse the creation time of a tag as a Unix timestamp
+pub fn created_timestamp(tag: &TagInfo) -> Option<i64> {
+    tag.created
+        .as_deref()
+        .and_then(|c| chrono::DateTime::parse_from_rfc3339(c).ok())
+        .map(|t| t.timestamp())
+}
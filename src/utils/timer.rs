@@ -0,0 +1,25 @@
+//! Async timers for desktop and web
+
+use std::time::Duration;
+
+/// Sleep without blocking the UI thread
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Sleep without blocking the UI thread
+#[cfg(target_arch = "wasm32")]
+pub async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
+/// Current Unix time in milliseconds
+pub fn unix_now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}